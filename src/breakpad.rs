@@ -0,0 +1,293 @@
+//! Parsing and formatting of records in Breakpad symbol files.
+//!
+//! Breakpad symbol files start with a `MODULE` record that identifies the debug file, which is
+//! usually followed by `INFO` records with additional information about the module, such as the
+//! `INFO CODE_ID` record. This module allows to read and write these records with the identifier
+//! types of this crate.
+
+use std::error;
+use std::fmt;
+use std::str;
+
+use crate::{
+    fmt_parse_error, map_offsets, CodeId, DebugId, ErrorInput, ParseErrorKind, ParseOptions,
+};
+
+/// Indicates an error parsing a Breakpad record.
+///
+/// Like other parse errors of this crate, this records the [`ParseErrorKind`] and the byte
+/// offset in the line at which parsing failed. Errors in identifiers are reported with the
+/// offset of the offending character in the line.
+///
+/// [`ParseErrorKind`]: ../enum.ParseErrorKind.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseRecordError {
+    kind: ParseErrorKind,
+    offset: usize,
+    input: Option<ErrorInput>,
+}
+
+impl ParseRecordError {
+    fn new(kind: ParseErrorKind, offset: usize) -> Self {
+        ParseRecordError {
+            kind,
+            offset,
+            input: None,
+        }
+    }
+
+    fn with_input(mut self, input: &str) -> Self {
+        if !input.is_empty() {
+            self.input = Some(ErrorInput::new(input));
+        }
+        self
+    }
+
+    /// Returns the reason why parsing failed.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset in the line at which parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the beginning of the rejected line.
+    ///
+    /// Long lines are truncated, see [`is_input_truncated`](#method.is_input_truncated).
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_ref().map(|input| input.buf.as_str())
+    }
+
+    /// Returns whether [`input`](#method.input) was truncated.
+    pub fn is_input_truncated(&self) -> bool {
+        self.input.map_or(false, |input| input.truncated)
+    }
+}
+
+impl error::Error for ParseRecordError {}
+
+impl fmt::Display for ParseRecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_parse_error(
+            f,
+            "breakpad record",
+            self.kind,
+            self.offset,
+            self.input.as_ref(),
+        )
+    }
+}
+
+/// Splits a record into whitespace-separated fields, keeping track of their offsets.
+struct Fields<'a> {
+    line: &'a str,
+    offset: usize,
+}
+
+impl<'a> Fields<'a> {
+    /// Removes the line terminator and checks the record type.
+    fn new(line: &'a str, record: &str) -> Result<Self, ParseRecordError> {
+        let line = line.trim_end_matches(|c| c == '\n' || c == '\r');
+        let mut fields = Fields { line, offset: 0 };
+
+        match fields.next() {
+            Some((_, name)) if name == record => Ok(fields),
+            Some(_) => Err(ParseRecordError::new(ParseErrorKind::InvalidFormat, 0)),
+            None => Err(ParseRecordError::new(ParseErrorKind::Empty, 0)),
+        }
+    }
+
+    /// Returns the next field and its offset.
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        let (start, rest) = self.rest()?;
+        let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        self.offset = start + len;
+        Some((start, &rest[..len]))
+    }
+
+    /// Returns the next field, or an error if the line ends.
+    fn expect(&mut self) -> Result<(usize, &'a str), ParseRecordError> {
+        let end = self.line.len();
+        self.next()
+            .ok_or_else(|| ParseRecordError::new(ParseErrorKind::InvalidFormat, end))
+    }
+
+    /// Returns the remainder of the line, or an error if the line ends.
+    fn expect_rest(&mut self) -> Result<(usize, &'a str), ParseRecordError> {
+        let end = self.line.len();
+        self.rest()
+            .ok_or_else(|| ParseRecordError::new(ParseErrorKind::InvalidFormat, end))
+    }
+
+    /// Returns the remainder of the line after skipping whitespace, if it is not empty.
+    ///
+    /// Trailing whitespace is removed from the remainder.
+    fn rest(&mut self) -> Option<(usize, &'a str)> {
+        let rest = self.line[self.offset..].trim_start();
+        let start = self.line.len() - rest.len();
+        self.offset = self.line.len();
+        match rest.trim_end() {
+            "" => None,
+            rest => Some((start, rest)),
+        }
+    }
+}
+
+/// Parses a debug identifier in breakpad format at the given offset in a record.
+fn parse_debug_id(string: &str, offset: usize) -> Result<DebugId, ParseRecordError> {
+    let result = DebugId::parse_str(string, ParseOptions::breakpad());
+    match map_offsets(result, |o| o + offset) {
+        Ok((debug_id, _)) => Ok(debug_id),
+        Err(error) => Err(ParseRecordError::new(error.kind(), error.offset())),
+    }
+}
+
+/// The `MODULE` record at the beginning of a Breakpad symbol file.
+///
+/// The record has the form `MODULE <os> <arch> <debug id> <name>`, where the debug identifier is
+/// in the breakpad format. The name is the file name of the debug file and may contain spaces.
+///
+/// # Example
+///
+/// ```
+/// use debugid::breakpad::ModuleRecord;
+///
+/// let line = "MODULE Linux x86_64 DFB8E43AF2423D73A453AEB6A777EF750 libfoo.so";
+/// let record = ModuleRecord::parse(line).unwrap();
+/// assert_eq!(record.os, "Linux");
+/// assert_eq!(record.arch, "x86_64");
+/// assert_eq!(record.name, "libfoo.so");
+/// assert_eq!(record.to_string(), line);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ModuleRecord {
+    /// The operating system, for example `Linux`, `mac` or `windows`.
+    pub os: String,
+    /// The CPU architecture, for example `x86_64` or `arm64`.
+    pub arch: String,
+    /// The debug identifier of the module.
+    pub debug_id: DebugId,
+    /// The file name of the debug file.
+    pub name: String,
+}
+
+impl ModuleRecord {
+    /// Parses a `MODULE` record from a line of a Breakpad symbol file.
+    ///
+    /// Fields may be separated by any amount of whitespace, and a trailing line terminator is
+    /// ignored. Whitespace within the name is retained.
+    pub fn parse(line: &str) -> Result<Self, ParseRecordError> {
+        Self::parse_fields(line).map_err(|error| error.with_input(line))
+    }
+
+    fn parse_fields(line: &str) -> Result<Self, ParseRecordError> {
+        let mut fields = Fields::new(line, "MODULE")?;
+        let (_, os) = fields.expect()?;
+        let (_, arch) = fields.expect()?;
+        let (id_offset, id) = fields.expect()?;
+        let debug_id = parse_debug_id(id, id_offset)?;
+        let (_, name) = fields.expect_rest()?;
+
+        Ok(ModuleRecord {
+            os: os.into(),
+            arch: arch.into(),
+            debug_id,
+            name: name.into(),
+        })
+    }
+}
+
+impl fmt::Display for ModuleRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MODULE {} {} {} {}",
+            self.os,
+            self.arch,
+            self.debug_id.breakpad(),
+            self.name
+        )
+    }
+}
+
+impl str::FromStr for ModuleRecord {
+    type Err = ParseRecordError;
+
+    fn from_str(string: &str) -> Result<Self, ParseRecordError> {
+        Self::parse(string)
+    }
+}
+
+/// The `INFO CODE_ID` record of a Breakpad symbol file.
+///
+/// The record has the form `INFO CODE_ID <code id> [<code file>]` and usually follows the
+/// `MODULE` record. The code file is the file name of the executable or library and is omitted
+/// by some producers.
+///
+/// # Example
+///
+/// ```
+/// use debugid::breakpad::CodeIdRecord;
+///
+/// let line = "INFO CODE_ID 5CCC38584B08000 ntdll.dll";
+/// let record = CodeIdRecord::parse(line).unwrap();
+/// assert_eq!(record.code_id.as_str(), "5ccc38584b08000");
+/// assert_eq!(record.code_file.as_deref(), Some("ntdll.dll"));
+/// assert_eq!(record.to_string(), line);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CodeIdRecord {
+    /// The code identifier of the module.
+    pub code_id: CodeId,
+    /// The file name of the code file, if present.
+    pub code_file: Option<String>,
+}
+
+impl CodeIdRecord {
+    /// Parses an `INFO CODE_ID` record from a line of a Breakpad symbol file.
+    ///
+    /// The code identifier must be hex-encoded, see [`CodeId::parse_hex`]. Fields may be
+    /// separated by any amount of whitespace, and a trailing line terminator is ignored.
+    ///
+    /// [`CodeId::parse_hex`]: ../struct.CodeId.html#method.parse_hex
+    pub fn parse(line: &str) -> Result<Self, ParseRecordError> {
+        Self::parse_fields(line).map_err(|error| error.with_input(line))
+    }
+
+    fn parse_fields(line: &str) -> Result<Self, ParseRecordError> {
+        let mut fields = Fields::new(line, "INFO")?;
+        match fields.expect()? {
+            (_, "CODE_ID") => (),
+            (offset, _) => {
+                return Err(ParseRecordError::new(ParseErrorKind::InvalidFormat, offset))
+            }
+        }
+
+        let (id_offset, id) = fields.expect()?;
+        let code_id = CodeId::parse_hex(id)
+            .map_err(|error| ParseRecordError::new(error.kind(), id_offset + error.offset()))?;
+        let code_file = fields.rest().map(|(_, code_file)| code_file.into());
+
+        Ok(CodeIdRecord { code_id, code_file })
+    }
+}
+
+impl fmt::Display for CodeIdRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "INFO CODE_ID {}", self.code_id.uppercase())?;
+        if let Some(ref code_file) = self.code_file {
+            write!(f, " {}", code_file)?;
+        }
+        Ok(())
+    }
+}
+
+impl str::FromStr for CodeIdRecord {
+    type Err = ParseRecordError;
+
+    fn from_str(string: &str) -> Result<Self, ParseRecordError> {
+        Self::parse(string)
+    }
+}
@@ -13,31 +13,572 @@
 //! [`DebugId`]: struct.DebugId.html
 
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+// Without the `std` feature, `std` paths resolve to `core`. Everything that allocates is gated.
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+
+use std::cmp::Ordering;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+#[cfg(feature = "std")]
 use std::error;
 use std::fmt;
 use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroU8;
+use std::ops::Deref;
 use std::str;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+use uuid::{Bytes, Uuid, Variant};
+
+#[cfg(all(feature = "serde", not(feature = "std")))]
+compile_error!("the `serde` feature requires the `std` feature");
+
+#[cfg(feature = "std")]
+pub mod breakpad;
+#[cfg(feature = "scan")]
+pub mod scan;
+#[cfg(feature = "std")]
+pub mod symkey;
+
+/// The reason why an identifier could not be parsed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input is empty.
+    Empty,
+    /// The input contains a character that is not a hex digit.
+    InvalidHex,
+    /// The input is too short or too long.
+    InvalidLength,
+    /// The input does not have the expected structure, for instance a misplaced separator.
+    InvalidFormat,
+    /// The input ends before the appendix of a debug identifier.
+    MissingAppendix,
+    /// The input contains unexpected characters after the identifier.
+    TrailingGarbage,
+    /// The input contains a character that is not ASCII or is not valid UTF-8.
+    ///
+    /// Identifiers only consist of ASCII characters, so this usually indicates an encoding error
+    /// in the input rather than a malformed identifier.
+    NonAscii,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::Empty => write!(f, "empty input"),
+            ParseErrorKind::InvalidHex => write!(f, "invalid hex digit"),
+            ParseErrorKind::InvalidLength => write!(f, "invalid length"),
+            ParseErrorKind::InvalidFormat => write!(f, "invalid format"),
+            ParseErrorKind::MissingAppendix => write!(f, "missing appendix"),
+            ParseErrorKind::TrailingGarbage => write!(f, "trailing characters"),
+            ParseErrorKind::NonAscii => write!(f, "non-ASCII character"),
+        }
+    }
+}
+
+/// A copy of the rejected input stored in parse errors, truncated to a fixed capacity.
+#[derive(Clone, Copy)]
+struct ErrorInput {
+    buf: StackBuffer,
+    truncated: bool,
+}
+
+impl ErrorInput {
+    fn new(input: &str) -> Self {
+        let mut len = input.len().min(ERROR_INPUT_CAPACITY);
+        while !input.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        let mut buf = StackBuffer::new();
+        buf.write_str(&input[..len]).ok();
+
+        ErrorInput {
+            buf,
+            truncated: len < input.len(),
+        }
+    }
+}
+
+impl PartialEq for ErrorInput {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf.as_str() == other.buf.as_str() && self.truncated == other.truncated
+    }
+}
+
+impl Eq for ErrorInput {}
+
+impl fmt::Debug for ErrorInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.buf.as_str())?;
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats a parse error, including the rejected input if available.
+fn fmt_parse_error(
+    f: &mut fmt::Formatter<'_>,
+    what: &str,
+    kind: ParseErrorKind,
+    offset: usize,
+    input: Option<&ErrorInput>,
+) -> fmt::Result {
+    write!(f, "invalid {}", what)?;
+    if let Some(input) = input {
+        write!(f, " {:?}", input)?;
+    }
 
-use uuid::{Bytes, Uuid};
+    match kind {
+        ParseErrorKind::Empty => write!(f, ": {}", kind),
+        _ => write!(f, ": {} at offset {}", kind, offset),
+    }
+}
 
 /// Indicates an error parsing a [`DebugId`](struct.DebugId.html).
+///
+/// The error records the [`ParseErrorKind`] and the byte offset in the input at which parsing
+/// failed. Errors returned from parsing strings also record the beginning of the rejected input,
+/// which is included in the error message.
+///
+/// [`ParseErrorKind`]: enum.ParseErrorKind.html
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct ParseDebugIdError;
+pub struct ParseDebugIdError {
+    kind: ParseErrorKind,
+    offset: usize,
+    input: Option<ErrorInput>,
+}
+
+impl ParseDebugIdError {
+    fn new(kind: ParseErrorKind, offset: usize) -> Self {
+        ParseDebugIdError {
+            kind,
+            offset,
+            input: None,
+        }
+    }
+
+    fn with_input(mut self, input: &str) -> Self {
+        if !input.is_empty() {
+            self.input = Some(ErrorInput::new(input));
+        }
+        self
+    }
+
+    /// Returns the reason why parsing failed.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset in the input at which parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
 
+    /// Returns the beginning of the rejected input.
+    ///
+    /// This is `None` if the input was empty or not a string. Long inputs are truncated, see
+    /// [`is_input_truncated`](#method.is_input_truncated).
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_ref().map(|input| input.buf.as_str())
+    }
+
+    /// Returns whether [`input`](#method.input) was truncated.
+    pub fn is_input_truncated(&self) -> bool {
+        self.input.map_or(false, |input| input.truncated)
+    }
+}
+
+#[cfg(feature = "std")]
 impl error::Error for ParseDebugIdError {}
 
 impl fmt::Display for ParseDebugIdError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "invalid debug identifier")
+        fmt_parse_error(
+            f,
+            "debug identifier",
+            self.kind,
+            self.offset,
+            self.input.as_ref(),
+        )
+    }
+}
+
+/// Indicates an error converting a [`DebugId`](struct.DebugId.html) into a UUID.
+///
+/// This is returned for PDB 2.0 identifiers, which do not contain a UUID.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TryFromDebugIdError;
+
+#[cfg(feature = "std")]
+impl error::Error for TryFromDebugIdError {}
+
+impl fmt::Display for TryFromDebugIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "debug identifier does not contain a uuid")
+    }
+}
+
+/// Namespaces for deterministic debug identifiers.
+///
+/// Use these with [`DebugId::from_namespace`] to derive version 5 UUIDs. Identifiers for the
+/// same name derived in the same namespace are always equal.
+///
+/// [`DebugId::from_namespace`]: ../struct.DebugId.html#method.from_namespace
+pub mod namespace {
+    use uuid::Uuid;
+
+    /// The RFC 4122 namespace for fully-qualified domain names.
+    pub const DNS: Uuid = Uuid::NAMESPACE_DNS;
+
+    /// The RFC 4122 namespace for URLs.
+    pub const URL: Uuid = Uuid::NAMESPACE_URL;
+
+    /// The RFC 4122 namespace for ISO OIDs.
+    pub const OID: Uuid = Uuid::NAMESPACE_OID;
+
+    /// The RFC 4122 namespace for X.500 DNs.
+    pub const X500: Uuid = Uuid::NAMESPACE_X500;
+}
+
+/// Helpers for the JSON output of `minidump-stackwalk`.
+#[cfg(feature = "std")]
+pub mod minidump {
+    use super::{CodeId, DebugId, ParseDebugIdError};
+
+    /// Parses the identifiers of a module in the JSON output of `minidump-stackwalk`.
+    ///
+    /// Takes the `debug_file`, `debug_id` and `code_id` fields of a module and returns its debug
+    /// and code identifier. Missing identifiers are emitted as empty strings or as zeros, which
+    /// are both returned as `None`. The debug identifier is also ignored if the module has no
+    /// debug file, since `minidump-stackwalk` then writes a placeholder.
+    ///
+    /// Debug identifiers that are present but cannot be parsed result in an error. Code
+    /// identifiers that are not hex-encoded are returned as `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::minidump::parse_module_ids;
+    ///
+    /// let (debug_id, code_id) = parse_module_ids(
+    ///     "ntdll.pdb",
+    ///     "DFB8E43AF2423D73A453AEB6A777EF751",
+    ///     "5CCC38584B08000",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(debug_id.unwrap().appendix(), 1);
+    /// assert_eq!(code_id.unwrap().as_str(), "5ccc38584b08000");
+    ///
+    /// let ids = parse_module_ids("", "000000000000000000000000000000000", "").unwrap();
+    /// assert_eq!(ids, (None, None));
+    /// ```
+    pub fn parse_module_ids(
+        debug_file: &str,
+        debug_id: &str,
+        code_id: &str,
+    ) -> Result<(Option<DebugId>, Option<CodeId>), ParseDebugIdError> {
+        let debug_id = match (debug_file.trim(), debug_id.trim()) {
+            ("", _) | (_, "") => None,
+            (_, debug_id) => Some(debug_id.parse::<DebugId>()?).filter(|id| !id.is_nil()),
+        };
+
+        let code_id = CodeId::parse_hex(code_id.trim())
+            .ok()
+            .filter(|id| id.as_str().bytes().any(|c| c != b'0'));
+
+        Ok((debug_id, code_id))
+    }
+}
+
+/// Object file formats with distinct rules for code and debug identifiers.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum FileFormat {
+    /// Executable and Linkable Format, used on Linux and other Unix-like systems.
+    Elf,
+    /// Mach object files, used on macOS and iOS.
+    MachO,
+    /// Portable Executable files and their PDB debug companion files, used on Windows.
+    Pe,
+    /// WebAssembly modules.
+    Wasm,
+}
+
+/// Controls whether PDB 2.0 identifiers are accepted when parsing.
+///
+/// See [`ParseOptions::pdb20`](struct.ParseOptions.html#method.pdb20).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Pdb20Policy {
+    /// Accept both PDB 2.0 and UUID-based identifiers.
+    Allow,
+    /// Accept only PDB 2.0 identifiers.
+    Require,
+    /// Accept only UUID-based identifiers.
+    Forbid,
+}
+
+impl Default for Pdb20Policy {
+    fn default() -> Self {
+        Pdb20Policy::Allow
+    }
+}
+
+/// Controls how appendices longer than eight hex digits are handled when parsing.
+///
+/// The appendix of a [`DebugId`] is a 32-bit integer, but some producers write longer
+/// appendices. See [`ParseOptions::appendix`](struct.ParseOptions.html#method.appendix).
+///
+/// [`DebugId`]: struct.DebugId.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AppendixMode {
+    /// Reject appendices longer than eight hex digits.
+    Strict,
+    /// Ignore all characters after the first eight hex digits of the appendix.
+    ///
+    /// The ignored characters are returned by
+    /// [`DebugId::parse_with_tail`](struct.DebugId.html#method.parse_with_tail).
+    Truncate,
+    /// Accept appendices of up to 16 hex digits and retain the low 32 bits.
+    ///
+    /// This interprets the appendix as a 64-bit integer, as written by some producers that store
+    /// the age in a wider field. Longer appendices are rejected.
+    Wide,
+}
+
+impl Default for AppendixMode {
+    fn default() -> Self {
+        AppendixMode::Truncate
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-struct ParseOptions {
+/// The prefix of UUIDs represented as URN.
+const URN_PREFIX: &str = "urn:uuid:";
+
+/// Options to control how [`DebugId`]s are parsed.
+///
+/// Use this with [`DebugId::parse_with`] to enforce stricter or looser formats than `FromStr`.
+/// The options returned by [`ParseOptions::new`] match the behavior of `FromStr`, and the options
+/// returned by [`ParseOptions::breakpad`] match [`DebugId::from_breakpad`].
+///
+/// # Example
+///
+/// ```
+/// use debugid::{DebugId, ParseOptions};
+///
+/// let options = ParseOptions::new().allow_hyphens(false).require_appendix(true);
+/// assert!(DebugId::parse_with("dfb8e43af2423d73a453aeb6a777ef75a", options).is_ok());
+/// assert!(DebugId::parse_with("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a", options).is_err());
+/// assert!(DebugId::parse_with("dfb8e43af2423d73a453aeb6a777ef75", options).is_err());
+/// ```
+///
+/// [`DebugId`]: struct.DebugId.html
+/// [`DebugId::parse_with`]: struct.DebugId.html#method.parse_with
+/// [`DebugId::from_breakpad`]: struct.DebugId.html#method.from_breakpad
+/// [`ParseOptions::new`]: struct.ParseOptions.html#method.new
+/// [`ParseOptions::breakpad`]: struct.ParseOptions.html#method.breakpad
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ParseOptions {
     allow_hyphens: bool,
     require_appendix: bool,
-    allow_tail: bool,
+    appendix: AppendixMode,
+    allow_braces: bool,
+    allow_urn: bool,
+    trim: bool,
+    pdb20: Pdb20Policy,
+    allow_appendix_separators: bool,
+}
+
+impl ParseOptions {
+    /// Returns the options used by `FromStr`.
+    ///
+    /// Hyphenated and compact identifiers are accepted, the appendix is optional, and appendices
+    /// longer than eight hex digits are truncated.
+    pub fn new() -> Self {
+        ParseOptions {
+            allow_hyphens: true,
+            require_appendix: false,
+            appendix: AppendixMode::Truncate,
+            allow_braces: true,
+            allow_urn: true,
+            trim: false,
+            pdb20: Pdb20Policy::Allow,
+            allow_appendix_separators: false,
+        }
+    }
+
+    /// Returns the options used by [`DebugId::parse_strict`].
+    ///
+    /// Hyphenated and compact identifiers are accepted, but the appendix must not be longer than
+    /// eight hex digits. Braces and URN prefixes are not accepted.
+    ///
+    /// [`DebugId::parse_strict`]: struct.DebugId.html#method.parse_strict
+    pub fn strict() -> Self {
+        ParseOptions {
+            allow_hyphens: true,
+            require_appendix: false,
+            appendix: AppendixMode::Strict,
+            allow_braces: false,
+            allow_urn: false,
+            trim: false,
+            pdb20: Pdb20Policy::Allow,
+            allow_appendix_separators: false,
+        }
+    }
+
+    /// Returns the options used by [`DebugId::from_breakpad`].
+    ///
+    /// Only compact identifiers with an appendix of at most eight hex digits are accepted.
+    ///
+    /// [`DebugId::from_breakpad`]: struct.DebugId.html#method.from_breakpad
+    pub fn breakpad() -> Self {
+        ParseOptions {
+            allow_hyphens: false,
+            require_appendix: true,
+            appendix: AppendixMode::Strict,
+            allow_braces: false,
+            allow_urn: false,
+            trim: false,
+            pdb20: Pdb20Policy::Allow,
+            allow_appendix_separators: false,
+        }
+    }
+
+    /// Sets whether hyphenated identifiers are accepted.
+    pub fn allow_hyphens(mut self, allow: bool) -> Self {
+        self.allow_hyphens = allow;
+        self
+    }
+
+    /// Sets whether identifiers must contain an appendix.
+    ///
+    /// This does not apply to PDB 2.0 identifiers, which always contain an appendix.
+    pub fn require_appendix(mut self, require: bool) -> Self {
+        self.require_appendix = require;
+        self
+    }
+
+    /// Sets whether characters after the first eight hex digits of the appendix are ignored.
+    ///
+    /// If disabled, longer appendices are rejected. This is a shorthand for
+    /// [`ParseOptions::appendix`] with [`AppendixMode::Truncate`] or [`AppendixMode::Strict`].
+    ///
+    /// [`ParseOptions::appendix`]: struct.ParseOptions.html#method.appendix
+    /// [`AppendixMode::Truncate`]: enum.AppendixMode.html#variant.Truncate
+    /// [`AppendixMode::Strict`]: enum.AppendixMode.html#variant.Strict
+    pub fn allow_tail(mut self, allow: bool) -> Self {
+        self.appendix = match allow {
+            true => AppendixMode::Truncate,
+            false => AppendixMode::Strict,
+        };
+        self
+    }
+
+    /// Sets how appendices longer than eight hex digits are handled.
+    ///
+    /// This does not apply to PDB 2.0 identifiers, whose length limits the appendix to eight
+    /// hex digits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::{AppendixMode, DebugId, ParseOptions};
+    ///
+    /// let input = "dfb8e43a-f242-3d73-a453-aeb6a777ef75-10000000a";
+    /// let options = ParseOptions::new().appendix(AppendixMode::Wide);
+    /// assert_eq!(DebugId::parse_with(input, options).unwrap().appendix(), 10);
+    ///
+    /// let options = ParseOptions::new().appendix(AppendixMode::Strict);
+    /// assert!(DebugId::parse_with(input, options).is_err());
+    /// ```
+    pub fn appendix(mut self, mode: AppendixMode) -> Self {
+        self.appendix = mode;
+        self
+    }
+
+    /// Sets whether the UUID may be enclosed in braces.
+    ///
+    /// This is the GUID syntax of many Windows tools, for example
+    /// `{dfb8e43a-f242-3d73-a453-aeb6a777ef75}`. The appendix follows the closing brace and may
+    /// optionally be separated by a hyphen.
+    pub fn allow_braces(mut self, allow: bool) -> Self {
+        self.allow_braces = allow;
+        self
+    }
+
+    /// Sets whether the identifier may be prefixed with `urn:uuid:`.
+    ///
+    /// UUIDs are represented as URNs in some XML-based formats, for example
+    /// `urn:uuid:dfb8e43a-f242-3d73-a453-aeb6a777ef75`. The prefix is matched case-insensitively.
+    pub fn allow_urn(mut self, allow: bool) -> Self {
+        self.allow_urn = allow;
+        self
+    }
+
+    /// Sets whether decorations of copied debugger output are removed before parsing.
+    ///
+    /// If enabled, a leading label terminated by a colon and whitespace, such as `UUID: `, is
+    /// skipped and all whitespace is removed from the remaining input. This is disabled by
+    /// default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::{DebugId, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().trim(true);
+    /// let id = DebugId::parse_with("  UUID: DFB8E43A-F242-3D73-A453-AEB6A777EF75\n", options);
+    /// assert_eq!(id.unwrap().to_string(), "dfb8e43a-f242-3d73-a453-aeb6a777ef75");
+    /// ```
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Sets whether PDB 2.0 identifiers are accepted.
+    ///
+    /// Strings of 9 to 16 hex digits are parsed as PDB 2.0 identifiers. Callers that know which
+    /// kind of identifier to expect can use this to reject the other kind.
+    pub fn pdb20(mut self, policy: Pdb20Policy) -> Self {
+        self.pdb20 = policy;
+        self
+    }
+
+    /// Sets whether the appendix may be separated by `+` or `.` instead of a hyphen.
+    ///
+    /// Some vendor crash formats write identifiers like
+    /// `dfb8e43a-f242-3d73-a453-aeb6a777ef75+a`. The separator is accepted after both hyphenated
+    /// and compact UUIDs, as well as in PDB 2.0 identifiers. Formatting always produces the
+    /// canonical representation. This is disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::{DebugId, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().allow_appendix_separators(true);
+    /// let id = DebugId::parse_with("dfb8e43a-f242-3d73-a453-aeb6a777ef75.a", options).unwrap();
+    /// assert_eq!(id.to_string(), "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a");
+    /// ```
+    pub fn allow_appendix_separators(mut self, allow: bool) -> Self {
+        self.allow_appendix_separators = allow;
+        self
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Unique identifier for debug information files and their debug information.
@@ -103,8 +644,16 @@ struct ParseOptions {
 /// As long the bytes were written using the same major version of this crate you will be
 /// able to read it again like this.
 ///
+/// # Ordering
+///
+/// Debug identifiers are ordered by the 16 bytes of their UUID in big-endian byte order first,
+/// then by their appendix. PDB 2.0 identifiers are compared by their invalid UUID as returned by
+/// [`DebugId::uuid`], and order after a UUID based identifier with the same bytes and appendix.
+/// This order is stable and will not change within the same major version of this crate.
+///
 /// [`CodeId`]: struct.CodeId.html
 /// [`DebugId::breakpad`]: struct.DebugId.html#method.breakpad
+/// [`DebugId::uuid`]: struct.DebugId.html#method.uuid
 // This needs to be backwards compatible also in its exact in-memory byte-layout since this
 // struct is directly mapped from disk in e.g. Symbolic SymCache formats.  The first version
 // of this struct was defined as:
@@ -124,7 +673,7 @@ struct ParseOptions {
 // - `1u8`: The first 4 bytes of the `bytes` field contain a big-endian u32, the remaining
 //   bytes are 0.
 #[repr(C, packed)]
-#[derive(Default, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[derive(Default, Eq, PartialEq, Hash, Clone, Copy)]
 pub struct DebugId {
     bytes: Bytes,
     appendix: u32,
@@ -153,10 +702,65 @@ impl DebugId {
         }
     }
 
+    /// Constructs a `DebugId` from the big-endian `u128` representation of its UUID.
+    ///
+    /// The appendix is zero, use [`DebugId::with_appendix`] to set it. This never constructs a
+    /// PDB 2.0 identifier, even if the value was obtained from one via [`DebugId::as_u128`].
+    ///
+    /// [`DebugId::with_appendix`]: struct.DebugId.html#method.with_appendix
+    /// [`DebugId::as_u128`]: struct.DebugId.html#method.as_u128
+    pub fn from_u128(value: u128) -> Self {
+        Self::from_uuid(Uuid::from_u128(value))
+    }
+
+    /// Constructs a deterministic `DebugId` from arbitrary data.
+    ///
+    /// The UUID is a version 5 UUID computed from the SHA-1 hash of `namespace` and `data`, and
+    /// the appendix is zero. The same inputs always produce the same identifier, which allows to
+    /// derive stable identifiers from file contents, for example for source maps.
+    ///
+    /// This is equivalent to [`DebugId::from_namespace`].
+    ///
+    /// [`DebugId::from_namespace`]: struct.DebugId.html#method.from_namespace
+    pub fn from_data(namespace: Uuid, data: &[u8]) -> Self {
+        Self::from_namespace(namespace, data)
+    }
+
+    /// Constructs a deterministic `DebugId` from a name within a namespace.
+    ///
+    /// The UUID is a version 5 UUID of `name` in the given `namespace`, and the appendix is zero.
+    /// See the [`namespace`] module for the standard namespaces.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::{namespace, DebugId};
+    ///
+    /// let id = DebugId::from_namespace(namespace::URL, b"https://example.com/app.min.js");
+    /// assert_eq!(id.to_string(), "29827a20-1539-5fcf-9d11-eab406b0bafc");
+    /// ```
+    ///
+    /// [`namespace`]: namespace/index.html
+    pub fn from_namespace(namespace: Uuid, name: &[u8]) -> Self {
+        Self::from_uuid(Uuid::new_v5(&namespace, name))
+    }
+
+    /// Constructs a random `DebugId`.
+    ///
+    /// The UUID is a random version 4 UUID and the appendix is zero. This requires the `rand`
+    /// feature.
+    #[cfg(feature = "rand")]
+    pub fn random() -> Self {
+        Self::from_uuid(Uuid::new_v4())
+    }
+
     /// Constructs a `DebugId` from a Microsoft little-endian GUID and age.
     pub fn from_guid_age(guid: &[u8], age: u32) -> Result<Self, ParseDebugIdError> {
         if guid.len() != 16 {
-            return Err(ParseDebugIdError);
+            return Err(ParseDebugIdError::new(
+                ParseErrorKind::InvalidLength,
+                guid.len().min(16),
+            ));
         }
 
         let uuid = Uuid::from_bytes([
@@ -167,6 +771,31 @@ impl DebugId {
         Ok(DebugId::from_parts(uuid, age))
     }
 
+    /// Parses a `DebugId` from the raw bytes of a CodeView record.
+    ///
+    /// This supports `RSDS` records (`CV_INFO_PDB70`), as found in the debug directory of PE
+    /// files and in the CodeView record of minidump modules, as well as the older `NB10` records
+    /// (`CV_INFO_PDB20`). The PDB path following the identifier is ignored.
+    pub fn from_codeview(data: &[u8]) -> Result<Self, ParseDebugIdError> {
+        let too_short = || ParseDebugIdError::new(ParseErrorKind::InvalidLength, data.len());
+
+        match data.get(..4) {
+            Some(b"RSDS") => {
+                let guid = data.get(4..20).ok_or_else(too_short)?;
+                let age = read_u32_le(data, 20).ok_or_else(too_short)?;
+                Self::from_guid_age(guid, age)
+            }
+            Some(b"NB10") => {
+                // The NB10 signature is followed by a 4 byte offset, which is always zero.
+                let timestamp = read_u32_le(data, 8).ok_or_else(too_short)?;
+                let age = read_u32_le(data, 12).ok_or_else(too_short)?;
+                Ok(Self::from_pdb20(timestamp, age))
+            }
+            Some(_) => Err(ParseDebugIdError::new(ParseErrorKind::InvalidFormat, 0)),
+            None => Err(too_short()),
+        }
+    }
+
     /// Constructs a `DebugId` from a PDB 2.0 timestamp and age.
     pub fn from_pdb20(timestamp: u32, age: u32) -> Self {
         // The big-endian byte-order here has to match the one used to read this number in
@@ -196,300 +825,3581 @@ impl DebugId {
         }
     }
 
-    /// Parses a breakpad identifier from a string.
-    pub fn from_breakpad(string: &str) -> Result<Self, ParseDebugIdError> {
-        let options = ParseOptions {
-            allow_hyphens: false,
-            require_appendix: true,
-            allow_tail: false,
-        };
-        Self::parse_str(string, options).ok_or(ParseDebugIdError)
-    }
-
-    /// Returns the UUID part of the code module's debug_identifier.
+    /// Constructs a `DebugId` for a .NET portable PDB file from its GUID.
     ///
-    /// If this is a debug identifier for the PDB 2.0 format an invalid UUID is returned
-    /// where only the first 4 bytes are filled in and the remainder of the bytes are 0.
-    /// This means the UUID has variant [`uuid::Variant::NCS`] and an unknown version,
-    /// [`Uuid::get_version`] will return `None`, which is not a valid UUID.
+    /// Portable PDBs do not have an age. Symbol servers key them by the GUID followed by
+    /// `FFFFFFFF`, so the appendix of the returned identifier is `u32::MAX`, which is formatted
+    /// accordingly by [`DebugId::symstore_key`] and the other key builders.
     ///
-    /// This may seem odd however does seem reasonable:
+    /// # Example
     ///
-    /// - Every [`DebugId`] can be represented as [`Uuid`] and will still mostly look
-    ///   reasonable e.g. in comparisons etc.
-    /// - The PDB 2.0 format is very old and very unlikely to appear practically.
-    pub fn uuid(&self) -> Uuid {
-        Uuid::from_bytes(self.bytes)
-    }
-
-    /// Returns the appendix part of the code module's debug identifier.
+    /// ```
+    /// use debugid::DebugId;
+    /// use uuid::Uuid;
     ///
-    /// On Windows, this is an incrementing counter to identify the build.
-    /// On all other platforms, this value will always be zero.
-    pub fn appendix(&self) -> u32 {
-        self.appendix
-    }
-
-    /// Returns whether this identifier is nil, i.e. it consists only of zeros.
-    pub fn is_nil(&self) -> bool {
-        self.bytes == [0u8; 16] && self.appendix == 0
-    }
-
-    /// Returns whether this identifier is from the PDB 2.0 format.
-    pub fn is_pdb20(&self) -> bool {
-        self.typ == 1
-    }
-
-    /// Returns a wrapper which when formatted via `fmt::Display` will format a
-    /// a breakpad identifier.
-    pub fn breakpad(&self) -> BreakpadFormat<'_> {
-        BreakpadFormat { inner: self }
+    /// let uuid = Uuid::parse_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+    /// let id = DebugId::portable_pdb(uuid);
+    /// assert!(id.is_portable_pdb());
+    /// assert_eq!(
+    ///     id.symstore_key("Foo.pdb"),
+    ///     "Foo.pdb/DFB8E43AF2423D73A453AEB6A777EF75FFFFFFFF/Foo.pdb"
+    /// );
+    /// ```
+    ///
+    /// [`DebugId::symstore_key`]: struct.DebugId.html#method.symstore_key
+    pub fn portable_pdb(uuid: Uuid) -> Self {
+        Self::from_parts(uuid, u32::MAX)
     }
 
-    fn parse_str(string: &str, options: ParseOptions) -> Option<Self> {
-        let is_hyphenated = string.get(8..9) == Some("-");
-        if is_hyphenated && !options.allow_hyphens || !string.is_ascii() {
-            return None;
+    /// Derives a `DebugId` from the code identifier of a file in the given format.
+    ///
+    /// This implements the platform rules for deriving debug identifiers:
+    ///
+    ///  - **ELF**: The debug identifier consists of the first 16 bytes of the GNU build id,
+    ///    interpreted as little-endian GUID. Shorter build ids are padded with zeros.
+    ///  - **MachO**: The debug identifier is the UUID of the code identifier, which must be
+    ///    exactly 16 bytes.
+    ///  - **WASM**: The debug identifier consists of the first 16 bytes of the build id without
+    ///    any byte swapping. Shorter build ids are padded with zeros.
+    ///  - **PE**: Debug identifiers cannot be derived from the code identifier, so this always
+    ///    returns an error.
+    ///
+    /// An error is also returned if the code identifier is empty or not a valid sequence of hex
+    /// encoded bytes.
+    #[cfg(feature = "std")]
+    pub fn try_from_code_id(
+        code_id: &CodeId,
+        format: FileFormat,
+    ) -> Result<Self, ParseDebugIdError> {
+        let hex = code_id.as_str().as_bytes();
+        if hex.is_empty() {
+            return Err(ParseDebugIdError::new(ParseErrorKind::Empty, 0));
         }
 
-        // Can the PDB 2.0 format match?  This can never be true for a valid UUID.
-        let min_len = if is_hyphenated { 10 } else { 9 };
-        let max_len = if is_hyphenated { 17 } else { 16 };
-        if min_len <= string.len() && string.len() <= max_len {
-            let timestamp_str = string.get(..8)?;
-            let timestamp = u32::from_str_radix(timestamp_str, 16).ok()?;
-            let appendix_str = match is_hyphenated {
-                true => string.get(9..)?,
-                false => string.get(8..)?,
-            };
-            let appendix = u32::from_str_radix(appendix_str, 16).ok()?;
-            return Some(Self::from_pdb20(timestamp, appendix));
+        if let Some(offset) = hex.iter().position(|c| !c.is_ascii_hexdigit()) {
+            let kind = invalid_hex_kind(hex[offset]);
+            return Err(ParseDebugIdError::new(kind, offset));
         }
 
-        let uuid_len = if is_hyphenated { 36 } else { 32 };
-        let uuid = string.get(..uuid_len)?.parse().ok()?;
-        if !options.require_appendix && string.len() == uuid_len {
-            return Some(Self::from_parts(uuid, 0));
+        if hex.len() % 2 != 0 {
+            return Err(ParseDebugIdError::new(
+                ParseErrorKind::InvalidLength,
+                hex.len(),
+            ));
         }
 
-        let mut appendix_str = &string[uuid_len..];
-        if is_hyphenated ^ appendix_str.starts_with('-') {
-            return None; // Require a hyphen if and only if we're hyphenated.
-        } else if is_hyphenated {
-            appendix_str = &appendix_str[1..]; // Skip the hyphen for parsing.
+        let mut bytes = [0u8; 16];
+        for (target, byte) in bytes.iter_mut().zip(decode_hex(hex)) {
+            *target = byte.unwrap_or_default();
         }
 
-        if options.allow_tail && appendix_str.len() > 8 {
-            appendix_str = &appendix_str[..8];
+        match format {
+            FileFormat::Elf => Self::from_guid_age(&bytes, 0),
+            FileFormat::MachO if hex.len() == 32 => Ok(Self::from(bytes)),
+            FileFormat::MachO => Err(ParseDebugIdError::new(
+                ParseErrorKind::InvalidLength,
+                hex.len().min(32),
+            )),
+            FileFormat::Wasm => Ok(Self::from(bytes)),
+            FileFormat::Pe => Err(ParseDebugIdError::new(ParseErrorKind::InvalidFormat, 0)),
         }
-
-        // Parse the appendix, which fails on empty strings.
-        let appendix = u32::from_str_radix(appendix_str, 16).ok()?;
-        Some(Self::from_parts(uuid, appendix))
     }
 
-    /// Returns the PDB 2.0 timestamp.
+    /// Parses a breakpad identifier from a string.
     ///
-    /// Only valid if you know this is a PDB 2.0 debug identifier.
+    /// Parsing is case-insensitive, so identifiers emitted in lowercase by tools such as Crashpad
+    /// are accepted. Use [`DebugId::breakpad`] to format the canonical uppercase representation.
+    ///
+    /// [`DebugId::breakpad`]: struct.DebugId.html#method.breakpad
+    pub fn from_breakpad(string: &str) -> Result<Self, ParseDebugIdError> {
+        Self::parse_with(string, ParseOptions::breakpad())
+    }
+
+    /// Parses a `DebugId` from ASCII bytes.
+    ///
+    /// This accepts the same representations as `FromStr`, but operates on a byte slice, such as
+    /// a line of a memory-mapped symbol index. Bytes outside the ASCII range are rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::DebugId;
+    ///
+    /// let id = DebugId::from_ascii(b"dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    /// assert_eq!(id.appendix(), 10);
+    /// ```
+    pub fn from_ascii(bytes: &[u8]) -> Result<Self, ParseDebugIdError> {
+        match str::from_utf8(bytes) {
+            Ok(string) => string.parse(),
+            Err(error) => Err(ParseDebugIdError::new(
+                ParseErrorKind::NonAscii,
+                error.valid_up_to(),
+            )),
+        }
+    }
+
+    /// Parses the debug file name and `DebugId` from a symbol server path.
+    ///
+    /// Symbol servers such as Microsoft's symstore or Mozilla's Tecken store files at paths of
+    /// the form `<debug file>/<id>/<file>`, where the identifier is in the breakpad format.
+    /// Only the last three components of the path are considered, so URLs and paths into a
+    /// local store are supported. Both forward slashes and backslashes separate components.
+    ///
+    /// Offsets in errors refer to the full path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::DebugId;
+    ///
+    /// let path = "wntdll.pdb/DFB8E43AF2423D73A453AEB6A777EF75a/wntdll.pd_";
+    /// let (debug_file, debug_id) = DebugId::parse_symbol_path(path).unwrap();
+    /// assert_eq!(debug_file, "wntdll.pdb");
+    /// assert_eq!(debug_id.appendix(), 10);
+    /// ```
+    pub fn parse_symbol_path(path: &str) -> Result<(&str, Self), ParseDebugIdError> {
+        let mut components = path.rsplitn(4, |c| c == '/' || c == '\\');
+        let file_name = components.next().unwrap_or_default();
+        let id = components.next().unwrap_or_default();
+        let debug_file = components.next().unwrap_or_default();
+
+        if debug_file.is_empty() || id.is_empty() || file_name.is_empty() {
+            let error = match path.is_empty() {
+                true => ParseDebugIdError::new(ParseErrorKind::Empty, 0),
+                false => ParseDebugIdError::new(ParseErrorKind::InvalidFormat, path.len()),
+            };
+            return Err(error.with_input(path));
+        }
+
+        let id_offset = path.len() - file_name.len() - 1 - id.len();
+        let result = Self::parse_str(id, ParseOptions::breakpad());
+        match map_offsets(result, |offset| id_offset + offset) {
+            Ok((debug_id, _)) => Ok((debug_file, debug_id)),
+            Err(error) => Err(error.with_input(path)),
+        }
+    }
+
+    /// Parses a `DebugId` from a line of `dwarfdump --uuid` or `otool -l` output.
+    ///
+    /// Accepts lines like `UUID: DFB8E43A-F242-3D73-A453-AEB6A777EF75 (arm64) /path/to/file` or
+    /// `uuid DFB8E43A-F242-3D73-A453-AEB6A777EF75`. The label is matched case-insensitively and
+    /// surrounding whitespace is ignored. If the identifier is followed by an architecture in
+    /// parentheses, it is returned as well. Anything after that is ignored.
+    ///
+    /// Offsets in errors refer to the full line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::DebugId;
+    ///
+    /// let line = "UUID: DFB8E43A-F242-3D73-A453-AEB6A777EF75 (arm64) /usr/lib/libfoo.dylib";
+    /// let (debug_id, arch) = DebugId::parse_uuid_line(line).unwrap();
+    /// assert_eq!(debug_id.to_string(), "dfb8e43a-f242-3d73-a453-aeb6a777ef75");
+    /// assert_eq!(arch, Some("arm64"));
+    /// ```
+    pub fn parse_uuid_line(line: &str) -> Result<(Self, Option<&str>), ParseDebugIdError> {
+        let content = line.trim_start();
+        let label_len = match content.get(..4) {
+            Some(label) if label.eq_ignore_ascii_case("uuid") => {
+                match content[4..].starts_with(':') {
+                    true => 5,
+                    false => 4,
+                }
+            }
+            _ => 0,
+        };
+
+        let rest = &content[label_len..];
+        let token = rest.trim_start();
+        let offset = line.len() - token.len();
+        let token_len = token.find(char::is_whitespace).unwrap_or(token.len());
+        let (token, rest) = token.split_at(token_len);
+
+        let result = match token.is_empty() {
+            true => Err(ParseDebugIdError::new(ParseErrorKind::Empty, 0)),
+            false => Self::parse_str(token, ParseOptions::strict()),
+        };
+
+        let debug_id = match map_offsets(result, |o| offset + o) {
+            Ok((debug_id, _)) => debug_id,
+            Err(error) => return Err(error.with_input(line)),
+        };
+
+        let arch = rest
+            .trim_start()
+            .strip_prefix('(')
+            .and_then(|rest| rest.find(')').map(|end| &rest[..end]))
+            .filter(|arch| !arch.is_empty());
+
+        Ok((debug_id, arch))
+    }
+
+    /// Parses a `DebugId` like `FromStr` and reports the representation of the input.
+    ///
+    /// This is intended for tools that tell users which kind of identifier they entered.
+    /// Identifiers without hyphens are reported as [`DebugIdFormat::Breakpad`] if they contain an
+    /// appendix and no lowercase letters in the UUID, and as [`DebugIdFormat::Compact`]
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::{DebugId, DebugIdFormat};
+    ///
+    /// let (_, format) = DebugId::parse_any("DFB8E43AF2423D73A453AEB6A777EF75a").unwrap();
+    /// assert_eq!(format, DebugIdFormat::Breakpad);
+    /// ```
+    ///
+    /// [`DebugIdFormat::Breakpad`]: enum.DebugIdFormat.html#variant.Breakpad
+    /// [`DebugIdFormat::Compact`]: enum.DebugIdFormat.html#variant.Compact
+    pub fn parse_any(string: &str) -> Result<(Self, DebugIdFormat), ParseDebugIdError> {
+        let debug_id: DebugId = string.parse()?;
+
+        let is_urn = string
+            .get(..URN_PREFIX.len())
+            .map_or(false, |prefix| prefix.eq_ignore_ascii_case(URN_PREFIX));
+
+        let format = if is_urn {
+            DebugIdFormat::Urn
+        } else if string.starts_with('{') {
+            DebugIdFormat::Braced
+        } else if debug_id.is_pdb20() {
+            DebugIdFormat::Pdb20
+        } else if string.get(8..9) == Some("-") {
+            DebugIdFormat::Hyphenated
+        } else if string.len() > 32 && !string[..32].bytes().any(|c| c.is_ascii_lowercase()) {
+            DebugIdFormat::Breakpad
+        } else {
+            DebugIdFormat::Compact
+        };
+
+        Ok((debug_id, format))
+    }
+
+    /// Parses a `DebugId` from a string, rejecting input that `FromStr` would normalize.
+    ///
+    /// Contrary to `FromStr`, appendices longer than eight hex digits are rejected instead of
+    /// truncated, and braces or URN prefixes are not removed. See [`ParseOptions::strict`] for
+    /// details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::DebugId;
+    ///
+    /// assert!(DebugId::parse_strict("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").is_ok());
+    /// assert!(DebugId::parse_strict("dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface1").is_err());
+    /// ```
+    ///
+    /// [`ParseOptions::strict`]: struct.ParseOptions.html#method.strict
+    pub fn parse_strict(string: &str) -> Result<Self, ParseDebugIdError> {
+        Self::parse_with(string, ParseOptions::strict())
+    }
+
+    /// Parses a `DebugId` from a string with the given options.
+    ///
+    /// See [`ParseOptions`] for a description of the available options.
+    ///
+    /// [`ParseOptions`]: struct.ParseOptions.html
+    pub fn parse_with(string: &str, options: ParseOptions) -> Result<Self, ParseDebugIdError> {
+        Self::parse_with_tail(string, options).map(|(debug_id, _)| debug_id)
+    }
+
+    /// Checks whether a string can be parsed into a `DebugId`.
+    ///
+    /// This accepts the same input as `FromStr`, but is cheaper than parsing when only the result
+    /// of the validation is needed, since rejected input is not recorded in an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::DebugId;
+    ///
+    /// assert!(DebugId::is_valid("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a"));
+    /// assert!(!DebugId::is_valid("dfb8e43a-f242-3d73"));
+    /// ```
+    pub fn is_valid(string: &str) -> bool {
+        Self::parse_str(string, ParseOptions::new()).is_ok()
+    }
+
+    /// Parses a sequence of strings into `DebugId`s.
+    ///
+    /// This returns an iterator that parses each string like `FromStr` and yields one result per
+    /// input. Parsing does not allocate, so this is suitable for processing large symbol indexes
+    /// line by line. Use [`DebugId::parse_with`] in an iterator adapter to parse with custom
+    /// options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::DebugId;
+    ///
+    /// let index = "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a\ninvalid\n418e89c3-1";
+    /// let results: Vec<_> = DebugId::parse_many(index.lines()).collect();
+    /// assert_eq!(results.len(), 3);
+    /// assert!(results[1].is_err());
+    /// ```
+    ///
+    /// [`DebugId::parse_with`]: struct.DebugId.html#method.parse_with
+    pub fn parse_many<'a, I>(lines: I) -> impl Iterator<Item = Result<Self, ParseDebugIdError>> + 'a
+    where
+        I: IntoIterator<Item = &'a str>,
+        I::IntoIter: 'a,
+    {
+        let options = ParseOptions::new();
+        lines
+            .into_iter()
+            .map(move |line| Self::parse_with(line, options))
+    }
+
+    /// Parses a `DebugId` from a string and returns the part of the input that was ignored.
+    ///
+    /// If [`ParseOptions::allow_tail`] is enabled, characters after the first eight hex digits
+    /// of the appendix are discarded. This returns these characters, so that lossy
+    /// normalizations can be detected. If [`ParseOptions::trim`] is enabled, trailing whitespace
+    /// is not part of the returned tail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::{DebugId, ParseOptions};
+    ///
+    /// let input = "dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface123";
+    /// let (id, tail) = DebugId::parse_with_tail(input, ParseOptions::new()).unwrap();
+    /// assert_eq!(id.appendix(), 0xfeed_face);
+    /// assert_eq!(tail, Some("123"));
+    /// ```
+    ///
+    /// [`ParseOptions::allow_tail`]: struct.ParseOptions.html#method.allow_tail
+    /// [`ParseOptions::trim`]: struct.ParseOptions.html#method.trim
+    pub fn parse_with_tail(
+        string: &str,
+        options: ParseOptions,
+    ) -> Result<(Self, Option<&str>), ParseDebugIdError> {
+        match Self::parse_str(string, options) {
+            Ok((debug_id, tail)) => {
+                let mut tail = tail.and_then(|offset| string.get(offset..));
+                if options.trim {
+                    tail = tail.map(str::trim_end);
+                }
+                Ok((debug_id, tail))
+            }
+            Err(error) => Err(error.with_input(string)),
+        }
+    }
+
+    /// Returns the UUID part of the code module's debug_identifier.
+    ///
+    /// If this is a debug identifier for the PDB 2.0 format an invalid UUID is returned
+    /// where only the first 4 bytes are filled in and the remainder of the bytes are 0.
+    /// This means the UUID has variant [`uuid::Variant::NCS`] and an unknown version,
+    /// [`Uuid::get_version`] will return `None`, which is not a valid UUID.
+    ///
+    /// This may seem odd however does seem reasonable:
+    ///
+    /// - Every [`DebugId`] can be represented as [`Uuid`] and will still mostly look
+    ///   reasonable e.g. in comparisons etc.
+    /// - The PDB 2.0 format is very old and very unlikely to appear practically.
+    pub fn uuid(&self) -> Uuid {
+        Uuid::from_bytes(self.bytes)
+    }
+
+    /// Returns the UUID part of the code module's debug identifier as big-endian `u128`.
+    ///
+    /// The appendix is not included and must be stored separately. To store the UUID in two
+    /// 64-bit columns, split the value into `(value >> 64) as u64` and `value as u64`.
+    ///
+    /// For PDB 2.0 identifiers, this returns the invalid UUID described in [`DebugId::uuid`],
+    /// where the timestamp occupies the most significant 32 bits. Converting this value back
+    /// with [`DebugId::from_u128`] does not restore a PDB 2.0 identifier.
+    ///
+    /// [`DebugId::uuid`]: struct.DebugId.html#method.uuid
+    /// [`DebugId::from_u128`]: struct.DebugId.html#method.from_u128
+    pub fn as_u128(&self) -> u128 {
+        self.uuid().as_u128()
+    }
+
+    /// Returns the UUID part of the code module's debug identifier, if there is one.
+    ///
+    /// Contrary to [`DebugId::uuid`], this returns `None` for PDB 2.0 identifiers, which do not
+    /// contain a UUID.
+    ///
+    /// [`DebugId::uuid`]: struct.DebugId.html#method.uuid
+    pub fn uuid_opt(&self) -> Option<Uuid> {
+        match self.is_pdb20() {
+            true => None,
+            false => Some(self.uuid()),
+        }
+    }
+
+    /// Returns the version number of the UUID part.
+    ///
+    /// This returns `None` for PDB 2.0 identifiers. UUIDs with an invalid version, such as
+    /// version 0, return their version number nevertheless so that validators can flag them.
+    pub fn uuid_version(&self) -> Option<usize> {
+        self.uuid_opt().map(|uuid| uuid.get_version_num())
+    }
+
+    /// Returns the variant of the UUID part.
+    ///
+    /// This returns `None` for PDB 2.0 identifiers.
+    pub fn uuid_variant(&self) -> Option<Variant> {
+        self.uuid_opt().map(|uuid| uuid.get_variant())
+    }
+
+    /// Returns the UUID part as Microsoft little-endian GUID.
+    ///
+    /// This is the inverse of [`DebugId::from_guid_age`] and returns the bytes in the mixed-endian
+    /// layout used by PDB headers and CodeView records. For PDB 2.0 identifiers, this is based on
+    /// the invalid UUID returned by [`DebugId::uuid`].
+    ///
+    /// [`DebugId::from_guid_age`]: struct.DebugId.html#method.from_guid_age
+    /// [`DebugId::uuid`]: struct.DebugId.html#method.uuid
+    pub fn guid(&self) -> [u8; 16] {
+        let b = self.bytes;
+        [
+            b[3], b[2], b[1], b[0], b[5], b[4], b[7], b[6], b[8], b[9], b[10], b[11], b[12], b[13],
+            b[14], b[15],
+        ]
+    }
+
+    /// Returns the age of a PDB debug identifier.
+    ///
+    /// This is an alias for [`DebugId::appendix`], using the terminology of PDB files.
+    ///
+    /// [`DebugId::appendix`]: struct.DebugId.html#method.appendix
+    pub fn age(&self) -> u32 {
+        self.appendix
+    }
+
+    /// Returns the appendix part of the code module's debug identifier.
+    ///
+    /// On Windows, this is an incrementing counter to identify the build.
+    /// On all other platforms, this value will always be zero.
+    pub fn appendix(&self) -> u32 {
+        self.appendix
+    }
+
+    /// Returns a copy of this identifier with the given appendix.
+    ///
+    /// The UUID part, or the timestamp for PDB 2.0 identifiers, is retained.
+    pub fn with_appendix(mut self, appendix: u32) -> Self {
+        self.appendix = appendix;
+        self
+    }
+
+    /// Returns a copy of this identifier with the appendix set to zero.
+    pub fn zero_appendix(self) -> Self {
+        self.with_appendix(0)
+    }
+
+    /// Returns a copy of this identifier with the appendix incremented by one.
+    ///
+    /// This is intended for PDB files, where the appendix is the age that increments every time
+    /// the PDB is written by the linker. Since ages start at one, this returns `None` for
+    /// identifiers with a zero appendix, which do not originate from PDB files. It also returns
+    /// `None` instead of wrapping around if the appendix is `u32::MAX`.
+    pub fn bump_appendix(&self) -> Option<Self> {
+        match self.appendix {
+            0 => None,
+            appendix => Some(self.with_appendix(appendix.checked_add(1)?)),
+        }
+    }
+
+    /// Returns whether this identifier is nil, i.e. it consists only of zeros.
+    pub fn is_nil(&self) -> bool {
+        self.bytes == [0u8; 16] && self.appendix == 0
+    }
+
+    /// Returns whether this identifier is from the PDB 2.0 format.
+    pub fn is_pdb20(&self) -> bool {
+        self.typ == 1
+    }
+
+    /// Returns whether this identifier follows the convention of .NET portable PDB files.
+    ///
+    /// See [`DebugId::portable_pdb`](struct.DebugId.html#method.portable_pdb).
+    pub fn is_portable_pdb(&self) -> bool {
+        !self.is_pdb20() && self.appendix == u32::MAX
+    }
+
+    /// Returns whether this identifier is plausible for a file of the given format.
+    ///
+    /// This checks format-specific constraints and is intended to detect obviously bogus
+    /// identifiers:
+    ///
+    ///  - Nil identifiers are never valid.
+    ///  - A non-zero appendix is only valid for PE files, where it contains the PDB age.
+    ///  - PDB 2.0 identifiers are only valid for PE files.
+    pub fn is_valid_for(&self, format: FileFormat) -> bool {
+        if self.is_nil() {
+            return false;
+        }
+
+        match format {
+            FileFormat::Pe => true,
+            _ => self.appendix == 0 && !self.is_pdb20(),
+        }
+    }
+
+    /// Returns whether this identifier could have been derived from the given code identifier.
+    ///
+    /// This checks the MachO and ELF rules of [`DebugId::try_from_code_id`]. Since the file
+    /// format is not known, identifiers with a non-zero appendix or PDB 2.0 identifiers are
+    /// assumed to originate from PE files and never match.
+    ///
+    /// [`DebugId::try_from_code_id`]: struct.DebugId.html#method.try_from_code_id
+    #[cfg(feature = "std")]
+    pub fn matches_code_id(&self, code_id: &CodeId) -> bool {
+        if self.is_pdb20() || self.appendix != 0 {
+            return false;
+        }
+
+        [FileFormat::MachO, FileFormat::Elf]
+            .iter()
+            .any(|&format| Self::try_from_code_id(code_id, format) == Ok(*self))
+    }
+
+    /// Returns a hash of this identifier that is stable across versions and platforms.
+    ///
+    /// Contrary to `std::hash::Hash`, the value returned by this function is guaranteed to never
+    /// change, which makes it suitable for sharding and persistent storage. It is computed as
+    /// 64-bit FNV-1a hash over the 16 UUID bytes as returned by [`DebugId::uuid`], the appendix
+    /// as big-endian `u32`, and a single byte that is `1` for PDB 2.0 identifiers and `0`
+    /// otherwise.
+    ///
+    /// [`DebugId::uuid`]: struct.DebugId.html#method.uuid
+    pub fn stable_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let bytes = self.bytes;
+        let appendix = self.appendix.to_be_bytes();
+        let typ = [self.typ];
+
+        let data = bytes.iter().chain(&appendix).chain(&typ);
+        data.fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+        })
+    }
+
+    /// Returns a wrapper which when formatted via `fmt::Display` will format a
+    /// a breakpad identifier.
+    pub fn breakpad(&self) -> BreakpadFormat<'_> {
+        BreakpadFormat {
+            inner: self,
+            upper_id: true,
+            upper_appendix: false,
+        }
+    }
+
+    /// Returns a wrapper which when formatted via `fmt::Display` will format the simple
+    /// representation.
+    ///
+    /// This is the 33 to 40 character lowercase representation without hyphens, which always
+    /// includes the appendix. It is equivalent to `id.breakpad().lowercase()` and to formatting
+    /// via `LowerHex`, and is used by symbol store layouts such as debuginfod.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use debugid::DebugId;
+    ///
+    /// let id = DebugId::from_str("DFB8E43A-F242-3D73-A453-AEB6A777EF75-A").unwrap();
+    /// assert_eq!(id.simple().to_string(), "dfb8e43af2423d73a453aeb6a777ef75a");
+    /// ```
+    pub fn simple(&self) -> BreakpadFormat<'_> {
+        self.breakpad().lowercase()
+    }
+
+    /// Returns the raw bytes of a CodeView record for this identifier.
+    ///
+    /// PDB 2.0 identifiers are written as `NB10` record, all other identifiers as `RSDS` record
+    /// (`CV_INFO_PDB70`). The `pdb_path` is appended as NUL-terminated string. This is the
+    /// inverse of [`DebugId::from_codeview`].
+    ///
+    /// [`DebugId::from_codeview`]: struct.DebugId.html#method.from_codeview
+    #[cfg(feature = "std")]
+    pub fn to_codeview(&self, pdb_path: &str) -> Vec<u8> {
+        let mut record = Vec::with_capacity(24 + pdb_path.len() + 1);
+
+        if self.is_pdb20() {
+            record.extend_from_slice(b"NB10");
+            record.extend_from_slice(&0u32.to_le_bytes());
+            record.extend_from_slice(&self.timestamp().to_le_bytes());
+        } else {
+            record.extend_from_slice(b"RSDS");
+            record.extend_from_slice(&self.guid());
+        }
+
+        record.extend_from_slice(&self.appendix().to_le_bytes());
+        record.extend_from_slice(pdb_path.as_bytes());
+        record.push(0);
+        record
+    }
+
+    /// Parses an identifier and returns the offset of the ignored tail, if any.
+    fn parse_str(string: &str, options: ParseOptions) -> ParseResult {
+        let error = ParseDebugIdError::new;
+
+        if string.is_empty() {
+            return Err(error(ParseErrorKind::Empty, 0));
+        }
+
+        if let Some(offset) = string.bytes().position(|c| !c.is_ascii()) {
+            return Err(error(ParseErrorKind::NonAscii, offset));
+        }
+
+        if options.trim {
+            return Self::parse_trimmed(string, options);
+        }
+
+        if options.allow_urn {
+            let prefix = string.get(..URN_PREFIX.len()).unwrap_or_default();
+            if prefix.eq_ignore_ascii_case(URN_PREFIX) {
+                let options = ParseOptions {
+                    allow_urn: false,
+                    ..options
+                };
+
+                let result = Self::parse_str(&string[URN_PREFIX.len()..], options);
+                return map_offsets(result, |offset| offset + URN_PREFIX.len());
+            }
+        }
+
+        if options.allow_braces && string.starts_with('{') {
+            return Self::parse_braced(string, options);
+        }
+
+        let is_hyphenated = string.get(8..9) == Some("-");
+        if is_hyphenated && !options.allow_hyphens {
+            return Err(error(ParseErrorKind::InvalidFormat, 8));
+        }
+
+        let is_separator = |s: &str| {
+            options.allow_appendix_separators && (s.starts_with('+') || s.starts_with('.'))
+        };
+
+        // Can the PDB 2.0 format match?  This can never be true for a valid UUID.
+        let is_separated = is_hyphenated || string.get(8..).map_or(false, is_separator);
+        let min_len = if is_separated { 10 } else { 9 };
+        let max_len = if is_separated { 17 } else { 16 };
+        let is_pdb20 = min_len <= string.len() && string.len() <= max_len;
+        if !is_pdb20 && options.pdb20 == Pdb20Policy::Require {
+            return Err(error(
+                ParseErrorKind::InvalidLength,
+                string.len().min(max_len),
+            ));
+        }
+
+        if is_pdb20 && options.pdb20 != Pdb20Policy::Forbid {
+            let timestamp = parse_hex_u32(&string[..8], 0)?;
+            let appendix_offset = if is_separated { 9 } else { 8 };
+            let appendix = parse_hex_u32(&string[appendix_offset..], appendix_offset)?;
+            return Ok((Self::from_pdb20(timestamp, appendix), None));
+        }
+
+        let uuid_len = if is_hyphenated { 36 } else { 32 };
+        for (offset, c) in string.bytes().take(uuid_len).enumerate() {
+            let expect_hyphen = is_hyphenated && [8, 13, 18, 23].contains(&offset);
+            if expect_hyphen && c != b'-' {
+                return Err(error(ParseErrorKind::InvalidFormat, offset));
+            } else if !expect_hyphen && !c.is_ascii_hexdigit() {
+                return Err(error(ParseErrorKind::InvalidHex, offset));
+            }
+        }
+
+        if string.len() < uuid_len {
+            return Err(error(ParseErrorKind::InvalidLength, string.len()));
+        }
+
+        // All digits have been validated above, so decode them directly.
+        let mut bytes = [0; 16];
+        let digits = string[..uuid_len].bytes().filter(|&c| c != b'-');
+        for (index, c) in digits.enumerate() {
+            let shift = if index % 2 == 0 { 4 } else { 0 };
+            bytes[index / 2] |= hex_value(c).unwrap_or_default() << shift;
+        }
+        let uuid = Uuid::from_bytes(bytes);
+
+        if string.len() == uuid_len {
+            return match options.require_appendix {
+                true => Err(error(ParseErrorKind::MissingAppendix, uuid_len)),
+                false => Ok((Self::from_parts(uuid, 0), None)),
+            };
+        }
+
+        // Require a hyphen if and only if we're hyphenated.
+        let mut appendix_offset = uuid_len;
+        if is_separator(&string[uuid_len..]) {
+            appendix_offset += 1; // Skip the alternative separator.
+        } else if is_hyphenated ^ string[uuid_len..].starts_with('-') {
+            return Err(error(ParseErrorKind::InvalidFormat, uuid_len));
+        } else if is_hyphenated {
+            appendix_offset += 1; // Skip the hyphen for parsing.
+        }
+
+        let mut appendix_str = &string[appendix_offset..];
+        if appendix_str.is_empty() {
+            return Err(error(ParseErrorKind::MissingAppendix, appendix_offset));
+        }
+
+        let mut tail = None;
+        if appendix_str.len() > 8 {
+            match options.appendix {
+                AppendixMode::Strict => {
+                    return Err(error(ParseErrorKind::TrailingGarbage, appendix_offset + 8));
+                }
+                AppendixMode::Truncate => {
+                    appendix_str = &appendix_str[..8];
+                    tail = Some(appendix_offset + 8);
+                }
+                AppendixMode::Wide if appendix_str.len() > 16 => {
+                    return Err(error(ParseErrorKind::TrailingGarbage, appendix_offset + 16));
+                }
+                AppendixMode::Wide => {
+                    // Validate the high digits, but only retain the low 32 bits.
+                    let high_len = appendix_str.len() - 8;
+                    parse_hex_u32(&appendix_str[..high_len], appendix_offset)?;
+                    appendix_str = &appendix_str[high_len..];
+                    appendix_offset += high_len;
+                }
+            }
+        }
+
+        let appendix = parse_hex_u32(appendix_str, appendix_offset)?;
+
+        Ok((Self::from_parts(uuid, appendix), tail))
+    }
+
+    /// Parses an identifier after removing labels and whitespace.
+    fn parse_trimmed(string: &str, options: ParseOptions) -> ParseResult {
+        let label_len = string
+            .char_indices()
+            .zip(string.chars().skip(1))
+            .find(|&((_, c), next)| c == ':' && next.is_whitespace())
+            .map_or(0, |((index, _), _)| index + 1);
+
+        let content = string[label_len..].trim_start();
+        let base = string.len() - content.len();
+        let content = content.trim_end();
+
+        // Valid identifiers are shorter than the buffer, so truncated input is always rejected
+        // or only loses characters that are ignored.
+        let mut buf = StackBuffer::new();
+        for c in content.chars().filter(|c| !c.is_whitespace()) {
+            if buf.write_char(c).is_err() {
+                break;
+            }
+        }
+
+        let options = ParseOptions {
+            trim: false,
+            ..options
+        };
+
+        map_offsets(Self::parse_str(buf.as_str(), options), |offset| {
+            let mut buf_offset = 0;
+            let content_offset = content
+                .char_indices()
+                .filter(|(_, c)| !c.is_whitespace())
+                .find(|(_, c)| {
+                    buf_offset += c.len_utf8();
+                    buf_offset > offset
+                })
+                .map_or(content.len(), |(index, _)| index);
+
+            base + content_offset
+        })
+    }
+
+    /// Parses a braced GUID by removing the braces and parsing the hyphenated representation.
+    fn parse_braced(string: &str, options: ParseOptions) -> ParseResult {
+        let (uuid_str, rest) = match (string.get(1..37), string.get(37..38)) {
+            (Some(uuid_str), Some("}")) => (uuid_str, &string[38..]),
+            _ => {
+                let offset = string.find('}').unwrap_or(string.len());
+                return Err(ParseDebugIdError::new(
+                    ParseErrorKind::InvalidFormat,
+                    offset,
+                ));
+            }
+        };
+
+        // Appendices longer than 16 hex digits are either truncated or rejected, so it is
+        // sufficient to retain the first characters of the appendix.
+        let has_separator = rest.starts_with('-')
+            || options.allow_appendix_separators
+                && (rest.starts_with('+') || rest.starts_with('.'));
+        let insert_hyphen = !rest.is_empty() && !has_separator;
+        let mut buf = StackBuffer::new();
+        buf.write_str(uuid_str).ok();
+        if insert_hyphen {
+            buf.write_char('-').ok();
+        }
+        buf.write_str(&rest[..rest.len().min(18)]).ok();
+
+        let options = ParseOptions {
+            allow_hyphens: true,
+            allow_braces: false,
+            ..options
+        };
+
+        map_offsets(
+            Self::parse_str(buf.as_str(), options),
+            |offset| match offset {
+                0..=35 => offset + 1,
+                _ if insert_hyphen => offset + 1,
+                _ => offset + 2,
+            },
+        )
+    }
+
+    /// Writes this identifier in the given style without allocating.
+    ///
+    /// This writes the same output as formatting via `Display`, [`DebugId::breakpad`] or
+    /// `LowerHex`, respectively, but allows to write directly into a buffer such as a stack
+    /// allocated array. Formatter flags are not supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use debugid::{DebugId, DebugIdStyle};
+    ///
+    /// let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    ///
+    /// let mut string = String::new();
+    /// id.write_to(&mut string, DebugIdStyle::Breakpad).unwrap();
+    /// assert_eq!(string, "DFB8E43AF2423D73A453AEB6A777EF75a");
+    /// ```
+    ///
+    /// [`DebugId::breakpad`]: struct.DebugId.html#method.breakpad
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W, style: DebugIdStyle) -> fmt::Result {
+        match style {
+            DebugIdStyle::Hyphenated => self.write_hyphenated(w),
+            DebugIdStyle::Compact => self.write_compact(w, false, false),
+            DebugIdStyle::Breakpad => self.write_compact(w, true, false),
+            DebugIdStyle::Symstore => self.write_compact(w, true, true),
+        }
+    }
+
+    /// Formats this identifier in the given style into an inline string.
+    ///
+    /// This is equivalent to [`DebugId::write_to`] with a `String`, but does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use debugid::{DebugId, DebugIdStyle};
+    ///
+    /// let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    /// let string = id.to_inline_string(DebugIdStyle::Breakpad);
+    /// assert_eq!(&*string, "DFB8E43AF2423D73A453AEB6A777EF75a");
+    /// ```
+    ///
+    /// [`DebugId::write_to`]: struct.DebugId.html#method.write_to
+    pub fn to_inline_string(&self, style: DebugIdStyle) -> DebugIdString {
+        let mut inner = StackBuffer::new();
+        // All styles are shorter than the capacity of the buffer.
+        self.write_to(&mut inner, style).ok();
+        DebugIdString { inner }
+    }
+
+    /// Returns the first `len` characters of the simple representation.
+    ///
+    /// This is intended for sharded directory layouts and object store prefixes. The simple
+    /// representation does not contain hyphens, so every character of the prefix is a hex digit,
+    /// see [`DebugId::simple`]. If `len` exceeds the length of the representation, the entire
+    /// representation is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use debugid::DebugId;
+    ///
+    /// let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    /// assert_eq!(id.shard_prefix(2), "df");
+    /// assert_eq!(id.shard_prefix(10), "dfb8e43af2");
+    /// ```
+    ///
+    /// [`DebugId::simple`]: struct.DebugId.html#method.simple
+    pub fn shard_prefix(&self, len: usize) -> DebugIdString {
+        let mut inner = StackBuffer::new();
+        // The simple representation is shorter than the capacity of the buffer.
+        write!(&mut inner, "{}", self.simple()).ok();
+        inner.len = inner.len.min(len);
+        DebugIdString { inner }
+    }
+
+    /// Returns all textual representations of this identifier.
+    ///
+    /// This is intended for diagnostics, where the same identifier needs to be looked up in
+    /// systems using different representations. Note that the path used by debuginfod servers
+    /// requires the full GNU build id, which can only be derived from a [`CodeId`].
+    ///
+    /// [`CodeId`]: struct.CodeId.html
+    #[cfg(feature = "std")]
+    pub fn representations(&self) -> DebugIdRepresentations {
+        let format = |style| {
+            let mut string = String::new();
+            // Writing into a `String` cannot fail.
+            self.write_to(&mut string, style).ok();
+            string
+        };
+
+        DebugIdRepresentations {
+            hyphenated: format(DebugIdStyle::Hyphenated),
+            compact: format(DebugIdStyle::Compact),
+            breakpad: format(DebugIdStyle::Breakpad),
+            symstore: format(DebugIdStyle::Symstore),
+        }
+    }
+
+    /// Writes the hyphenated representation, which omits a zero appendix except for PDB 2.0.
+    fn write_hyphenated<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        match self.is_pdb20() {
+            true => write!(w, "{:08X}", self.timestamp())?,
+            false => write!(w, "{}", self.uuid().hyphenated())?,
+        }
+
+        // PDB 2.0 identifiers always include the appendix, since the timestamp alone is too short
+        // to be parsed.
+        if self.appendix > 0 || self.is_pdb20() {
+            write!(w, "-{:x}", { self.appendix })?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the compact representation without hyphens, which always includes the appendix.
+    fn write_compact<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        upper_id: bool,
+        upper_appendix: bool,
+    ) -> fmt::Result {
+        match (self.is_pdb20(), upper_id) {
+            (true, true) => write!(w, "{:08X}", self.timestamp())?,
+            (true, false) => write!(w, "{:08x}", self.timestamp())?,
+            (false, true) => write!(w, "{:X}", self.uuid().simple())?,
+            (false, false) => write!(w, "{:x}", self.uuid().simple())?,
+        }
+
+        match upper_appendix {
+            true => write!(w, "{:X}", self.appendix()),
+            false => write!(w, "{:x}", self.appendix()),
+        }
+    }
+
+    /// Returns the PDB 2.0 timestamp.
+    ///
+    /// Only valid if you know this is a PDB 2.0 debug identifier.
     fn timestamp(&self) -> u32 {
         u32::from_be_bytes([self.bytes[0], self.bytes[1], self.bytes[2], self.bytes[3]])
     }
 }
 
-impl fmt::Debug for DebugId {
+/// The capacity of a `StackBuffer` in bytes.
+///
+/// This fits a hyphenated identifier with a 16 digit appendix, as accepted by
+/// `AppendixMode::Wide`, including the characters needed to detect longer input.
+const STACK_BUFFER_CAPACITY: usize = 64;
+
+/// The maximum length of the input recorded in parse errors.
+const ERROR_INPUT_CAPACITY: usize = 48;
+
+/// A fixed-capacity buffer to format identifiers without allocating.
+///
+/// Writing more than the capacity fails with `fmt::Error`. Apart from the inputs recorded in parse
+/// errors, only ASCII is ever written into this buffer by this crate.
+#[derive(Clone, Copy)]
+struct StackBuffer {
+    bytes: [u8; STACK_BUFFER_CAPACITY],
+    len: usize,
+}
+
+impl StackBuffer {
+    fn new() -> Self {
+        StackBuffer {
+            bytes: [0; STACK_BUFFER_CAPACITY],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only complete `str`s are written in `write_str`, so this is always valid UTF-8.
+        str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+/// Writes the output of `write` to the formatter, honoring its width, fill and alignment.
+///
+/// The output is buffered in a [`StackBuffer`] and only spills to the heap if it does not fit.
+#[cfg(feature = "std")]
+fn pad_with<F>(f: &mut fmt::Formatter<'_>, write: F) -> fmt::Result
+where
+    F: Fn(&mut dyn fmt::Write) -> fmt::Result,
+{
+    let mut buf = StackBuffer::new();
+    if write(&mut buf).is_ok() {
+        return f.pad(buf.as_str());
+    }
+
+    let mut string = String::new();
+    write(&mut string)?;
+    f.pad(&string)
+}
+
+impl fmt::Write for StackBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// The number of bytes of a [`CodeId`] stored inline.
+///
+/// This fits the hex representation of 20-byte identifiers, such as SHA-1 GNU build ids, and
+/// keeps `CodeId` at 56 bytes.
+#[cfg(feature = "std")]
+const CODE_ID_INLINE_CAPACITY: usize = 40;
+
+/// The string representation of a [`CodeId`].
+///
+/// Code identifiers are short and almost always fit inline, which avoids heap allocations.
+/// Longer strings spill to the heap.
+#[cfg(feature = "std")]
+#[derive(Clone)]
+enum CodeIdBuf {
+    Inline {
+        bytes: [u8; CODE_ID_INLINE_CAPACITY],
+        len: u8,
+    },
+    Heap(String),
+}
+
+#[cfg(feature = "std")]
+impl CodeIdBuf {
+    fn as_str(&self) -> &str {
+        match self {
+            // Only complete `str`s are written inline, so this is always valid UTF-8.
+            CodeIdBuf::Inline { bytes, len } => {
+                str::from_utf8(&bytes[..*len as usize]).unwrap_or_default()
+            }
+            CodeIdBuf::Heap(string) => string.as_str(),
+        }
+    }
+
+    fn make_ascii_lowercase(&mut self) {
+        match self {
+            CodeIdBuf::Inline { bytes, len } => bytes[..*len as usize].make_ascii_lowercase(),
+            CodeIdBuf::Heap(string) => string.make_ascii_lowercase(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for CodeIdBuf {
+    fn default() -> Self {
+        CodeIdBuf::Inline {
+            bytes: [0; CODE_ID_INLINE_CAPACITY],
+            len: 0,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<String> for CodeIdBuf {
+    fn from(string: String) -> Self {
+        if string.len() > CODE_ID_INLINE_CAPACITY {
+            return CodeIdBuf::Heap(string);
+        }
+
+        let mut buf = CodeIdBuf::default();
+        // Writing into a `CodeIdBuf` cannot fail.
+        buf.write_str(&string).ok();
+        buf
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Write for CodeIdBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self {
+            CodeIdBuf::Inline { bytes, len } => {
+                let start = *len as usize;
+                let end = start + s.len();
+                if end <= CODE_ID_INLINE_CAPACITY {
+                    bytes[start..end].copy_from_slice(s.as_bytes());
+                    *len = end as u8;
+                } else {
+                    let mut string = String::with_capacity(end);
+                    string.push_str(self.as_str());
+                    string.push_str(s);
+                    *self = CodeIdBuf::Heap(string);
+                }
+            }
+            CodeIdBuf::Heap(string) => string.push_str(s),
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the descriptor of the first non-empty GNU build id note in the given ELF notes.
+#[cfg(feature = "std")]
+fn find_gnu_build_id(data: &[u8], read_u32: fn(&[u8], usize) -> Option<u32>) -> Option<&[u8]> {
+    const NT_GNU_BUILD_ID: u32 = 3;
+
+    // Names and descriptors are padded to a 4-byte alignment.
+    let align = |size: u32| (size as usize + 3) & !3;
+
+    let mut offset = 0;
+    while offset < data.len() {
+        let name_size = read_u32(data, offset)?;
+        let desc_size = read_u32(data, offset + 4)?;
+        let note_type = read_u32(data, offset + 8)?;
+
+        let name_offset = offset + 12;
+        let desc_offset = name_offset.checked_add(align(name_size))?;
+        let desc = data.get(desc_offset..desc_offset.checked_add(desc_size as usize)?)?;
+
+        let name = data.get(name_offset..name_offset + name_size as usize)?;
+        if note_type == NT_GNU_BUILD_ID && name == b"GNU\0" && !desc.is_empty() {
+            return Some(desc);
+        }
+
+        offset = desc_offset.checked_add(align(desc_size))?;
+    }
+
+    None
+}
+
+/// The result of parsing a [`DebugId`] and the offset of the ignored tail of the input.
+type ParseResult = Result<(DebugId, Option<usize>), ParseDebugIdError>;
+
+/// Maps the offsets in a [`ParseResult`] back to the original input.
+fn map_offsets(result: ParseResult, map: impl Fn(usize) -> usize) -> ParseResult {
+    match result {
+        Ok((debug_id, tail)) => Ok((debug_id, tail.map(&map))),
+        Err(mut error) => {
+            error.offset = map(error.offset);
+            Err(error)
+        }
+    }
+}
+
+/// Parses a hex encoded `u32` located at `offset` in the input.
+fn parse_hex_u32(hex: &str, offset: usize) -> Result<u32, ParseDebugIdError> {
+    if let Some(position) = hex.bytes().position(|c| !c.is_ascii_hexdigit()) {
+        return Err(ParseDebugIdError::new(
+            ParseErrorKind::InvalidHex,
+            offset + position,
+        ));
+    }
+
+    u32::from_str_radix(hex, 16)
+        .map_err(|_| ParseDebugIdError::new(ParseErrorKind::InvalidLength, offset))
+}
+
+/// Decodes pairs of ASCII hex digits into bytes.
+///
+/// A trailing odd digit is ignored.
+#[cfg(feature = "std")]
+fn decode_hex(hex: &[u8]) -> impl Iterator<Item = Option<u8>> + '_ {
+    hex.chunks_exact(2)
+        .map(|pair| Some(hex_value(pair[0])? << 4 | hex_value(pair[1])?))
+}
+
+/// Returns the error kind for a byte that is not a hex digit.
+fn invalid_hex_kind(c: u8) -> ParseErrorKind {
+    match c.is_ascii() {
+        true => ParseErrorKind::InvalidHex,
+        false => ParseErrorKind::NonAscii,
+    }
+}
+
+/// Returns the value of an ASCII hex digit.
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Reads a little-endian `u32` at the given offset.
+fn read_u32_le(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+impl Ord for DebugId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Copy out of the packed struct to avoid references to unaligned fields.
+        let (bytes, appendix, typ) = (self.bytes, self.appendix, self.typ);
+        let (other_bytes, other_appendix, other_typ) = (other.bytes, other.appendix, other.typ);
+
+        bytes
+            .cmp(&other_bytes)
+            .then(appendix.cmp(&other_appendix))
+            .then(typ.cmp(&other_typ))
+    }
+}
+
+impl PartialOrd for DebugId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Reads a big-endian `u32` at the given offset.
+#[cfg(feature = "std")]
+fn read_u32_be(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+impl fmt::Debug for DebugId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut uuid = StackBuffer::new();
+        write!(&mut uuid, "{}", self.uuid().hyphenated())?;
+        f.debug_struct("DebugId")
+            .field("uuid", &uuid.as_str())
+            .field("appendix", &self.appendix())
+            .finish()
+    }
+}
+
+/// Formats the identifier honoring width, fill and alignment.
+///
+/// Use the alternate flag `{:#}` to format the breakpad representation, which is equivalent to
+/// formatting [`DebugId::breakpad`].
+///
+/// [`DebugId::breakpad`]: struct.DebugId.html#method.breakpad
+impl fmt::Display for DebugId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return self.breakpad().fmt(f);
+        }
+
+        let mut buf = StackBuffer::new();
+        self.write_to(&mut buf, DebugIdStyle::Hyphenated)?;
+        f.pad(buf.as_str())
+    }
+}
+
+/// Formats the compact representation in lowercase.
+///
+/// This is the 33 to 40 character representation without hyphens, which always includes the
+/// appendix. It is equivalent to a lowercase breakpad identifier. The alternate flag `{:#x}`
+/// produces the same output and does not add a `0x` prefix, so it can be used in generic code
+/// that formats the alternate form of several values.
+impl fmt::LowerHex for DebugId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = StackBuffer::new();
+        self.write_to(&mut buf, DebugIdStyle::Compact)?;
+        f.pad(buf.as_str())
+    }
+}
+
+/// Formats the compact representation in uppercase.
+///
+/// This is the 33 to 40 character representation without hyphens, which always includes the
+/// appendix. Contrary to the breakpad representation, the appendix is also uppercase.
+impl fmt::UpperHex for DebugId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = StackBuffer::new();
+        self.write_to(&mut buf, DebugIdStyle::Symstore)?;
+        f.pad(buf.as_str())
+    }
+}
+
+impl str::FromStr for DebugId {
+    type Err = ParseDebugIdError;
+
+    fn from_str(string: &str) -> Result<Self, ParseDebugIdError> {
+        Self::parse_with(string, ParseOptions::new())
+    }
+}
+
+/// Compares the identifier to its string representation.
+///
+/// The string is parsed like via `FromStr`, so all accepted representations compare equal.
+/// Strings that cannot be parsed never compare equal.
+impl PartialEq<str> for DebugId {
+    fn eq(&self, other: &str) -> bool {
+        other.parse::<DebugId>() == Ok(*self)
+    }
+}
+
+impl<'a> PartialEq<&'a str> for DebugId {
+    fn eq(&self, other: &&'a str) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialEq<DebugId> for str {
+    fn eq(&self, other: &DebugId) -> bool {
+        *other == *self
+    }
+}
+
+impl PartialEq<DebugId> for &str {
+    fn eq(&self, other: &DebugId) -> bool {
+        *other == **self
+    }
+}
+
+impl From<Uuid> for DebugId {
+    fn from(uuid: Uuid) -> Self {
+        DebugId::from_uuid(uuid)
+    }
+}
+
+impl From<(Uuid, u32)> for DebugId {
+    fn from(tuple: (Uuid, u32)) -> Self {
+        let (uuid, appendix) = tuple;
+        DebugId::from_parts(uuid, appendix)
+    }
+}
+
+impl From<Bytes> for DebugId {
+    fn from(bytes: Bytes) -> Self {
+        DebugId::from_uuid(Uuid::from_bytes(bytes))
+    }
+}
+
+impl TryFrom<DebugId> for (Uuid, u32) {
+    type Error = TryFromDebugIdError;
+
+    fn try_from(debug_id: DebugId) -> Result<Self, Self::Error> {
+        if debug_id.is_pdb20() {
+            return Err(TryFromDebugIdError);
+        }
+
+        Ok((debug_id.uuid(), debug_id.appendix()))
+    }
+}
+
+/// Textual representations of a [`DebugId`].
+///
+/// Used with [`DebugId::write_to`].
+///
+/// [`DebugId`]: struct.DebugId.html
+/// [`DebugId::write_to`]: struct.DebugId.html#method.write_to
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DebugIdStyle {
+    /// The hyphenated representation, for example `dfb8e43a-f242-3d73-a453-aeb6a777ef75-a`.
+    ///
+    /// This is the representation used by `Display`. A zero appendix is omitted, except for PDB
+    /// 2.0 identifiers.
+    Hyphenated,
+    /// The lowercase representation without hyphens, for example
+    /// `dfb8e43af2423d73a453aeb6a777ef75a`.
+    ///
+    /// This is the representation used by `LowerHex`. The appendix is always included.
+    Compact,
+    /// The breakpad representation, for example `DFB8E43AF2423D73A453AEB6A777EF75a`.
+    ///
+    /// This is the representation used by [`BreakpadFormat`]. The appendix is always included.
+    ///
+    /// [`BreakpadFormat`]: struct.BreakpadFormat.html
+    Breakpad,
+    /// The uppercase representation without hyphens used by Microsoft symbol servers, for
+    /// example `DFB8E43AF2423D73A453AEB6A777EF75A`.
+    ///
+    /// This is the representation used by `UpperHex`. The appendix is always included.
+    Symstore,
+}
+
+/// The representation of a parsed [`DebugId`].
+///
+/// Returned by [`DebugId::parse_any`].
+///
+/// [`DebugId`]: struct.DebugId.html
+/// [`DebugId::parse_any`]: struct.DebugId.html#method.parse_any
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DebugIdFormat {
+    /// The hyphenated representation, for example `dfb8e43a-f242-3d73-a453-aeb6a777ef75-a`.
+    Hyphenated,
+    /// The representation without hyphens, for example `dfb8e43af2423d73a453aeb6a777ef75a`.
+    Compact,
+    /// The breakpad representation with an uppercase UUID and an appendix, for example
+    /// `DFB8E43AF2423D73A453AEB6A777EF75a`.
+    Breakpad,
+    /// A PDB 2.0 identifier, for example `418E89C3-1` or `418E89C31`.
+    Pdb20,
+    /// A GUID enclosed in braces, for example `{dfb8e43a-f242-3d73-a453-aeb6a777ef75}`.
+    Braced,
+    /// A URN, for example `urn:uuid:dfb8e43a-f242-3d73-a453-aeb6a777ef75`.
+    Urn,
+}
+
+impl fmt::Display for DebugIdFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DebugIdFormat::Hyphenated => write!(f, "hyphenated"),
+            DebugIdFormat::Compact => write!(f, "compact"),
+            DebugIdFormat::Breakpad => write!(f, "breakpad"),
+            DebugIdFormat::Pdb20 => write!(f, "PDB 2.0"),
+            DebugIdFormat::Braced => write!(f, "braced GUID"),
+            DebugIdFormat::Urn => write!(f, "URN"),
+        }
+    }
+}
+
+/// The textual representation of a [`DebugId`] stored inline.
+///
+/// Returned by [`DebugId::to_inline_string`]. This dereferences to `str` and never allocates.
+/// The longest representation, a hyphenated identifier with an eight digit appendix, is 45
+/// bytes long.
+///
+/// [`DebugId`]: struct.DebugId.html
+/// [`DebugId::to_inline_string`]: struct.DebugId.html#method.to_inline_string
+#[derive(Clone, Copy)]
+pub struct DebugIdString {
+    inner: StackBuffer,
+}
+
+impl DebugIdString {
+    /// Returns the string representation.
+    pub fn as_str(&self) -> &str {
+        self.inner.as_str()
+    }
+}
+
+impl Deref for DebugIdString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for DebugIdString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for DebugIdString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for DebugIdString {}
+
+impl PartialEq<str> for DebugIdString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for DebugIdString {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Hash for DebugIdString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl Ord for DebugIdString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialOrd for DebugIdString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for DebugIdString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for DebugIdString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<DebugIdString> for String {
+    fn from(string: DebugIdString) -> Self {
+        string.as_str().into()
+    }
+}
+
+/// All textual representations of a [`DebugId`].
+///
+/// Returned by [`DebugId::representations`]. When formatted via `Display`, this prints one
+/// representation per line, which is useful for diagnostic output.
+///
+/// [`DebugId`]: struct.DebugId.html
+/// [`DebugId::representations`]: struct.DebugId.html#method.representations
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DebugIdRepresentations {
+    /// The hyphenated representation, see [`DebugIdStyle::Hyphenated`].
+    ///
+    /// [`DebugIdStyle::Hyphenated`]: enum.DebugIdStyle.html#variant.Hyphenated
+    pub hyphenated: String,
+    /// The compact representation, see [`DebugIdStyle::Compact`].
+    ///
+    /// [`DebugIdStyle::Compact`]: enum.DebugIdStyle.html#variant.Compact
+    pub compact: String,
+    /// The breakpad representation, see [`DebugIdStyle::Breakpad`].
+    ///
+    /// [`DebugIdStyle::Breakpad`]: enum.DebugIdStyle.html#variant.Breakpad
+    pub breakpad: String,
+    /// The symbol server representation, see [`DebugIdStyle::Symstore`].
+    ///
+    /// [`DebugIdStyle::Symstore`]: enum.DebugIdStyle.html#variant.Symstore
+    pub symstore: String,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for DebugIdRepresentations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "hyphenated: {}", self.hyphenated)?;
+        writeln!(f, "compact:    {}", self.compact)?;
+        writeln!(f, "breakpad:   {}", self.breakpad)?;
+        writeln!(f, "symstore:   {}", self.symstore)
+    }
+}
+
+/// Wrapper around [`DebugId`] for Breakpad formatting.
+///
+/// **Example:**
+///
+/// ```
+/// # extern crate debugid;
+/// use std::str::FromStr;
+/// use debugid::DebugId;
+///
+/// # fn foo() -> Result<(), debugid::ParseDebugIdError> {
+/// let id = DebugId::from_breakpad("DFB8E43AF2423D73A453AEB6A777EF75a")?;
+/// assert_eq!("DFB8E43AF2423D73A453AEB6A777EF75a".to_string(), id.breakpad().to_string());
+/// assert_eq!("dfb8e43af2423d73a453aeb6a777ef75a", id.breakpad().lowercase().to_string());
+/// # Ok(())
+/// # }
+///
+/// # fn main() { foo().unwrap() }
+/// ```
+///
+/// [`DebugId`]: struct.DebugId.html
+#[derive(Debug)]
+pub struct BreakpadFormat<'a> {
+    inner: &'a DebugId,
+    upper_id: bool,
+    upper_appendix: bool,
+}
+
+impl<'a> BreakpadFormat<'a> {
+    /// Formats the entire identifier in lowercase.
+    ///
+    /// By default, the UUID is formatted in uppercase and the appendix in lowercase.
+    pub fn lowercase(mut self) -> Self {
+        self.upper_id = false;
+        self.upper_appendix = false;
+        self
+    }
+
+    /// Formats the entire identifier in uppercase.
+    ///
+    /// By default, the UUID is formatted in uppercase and the appendix in lowercase.
+    pub fn uppercase(mut self) -> Self {
+        self.upper_id = true;
+        self.upper_appendix = true;
+        self
+    }
+}
+
+impl<'a> fmt::Display for BreakpadFormat<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = StackBuffer::new();
+        self.inner
+            .write_compact(&mut buf, self.upper_id, self.upper_appendix)?;
+        f.pad(buf.as_str())
+    }
+}
+
+/// A [`DebugId`] that is known not to be nil.
+///
+/// This works like `NonZeroU32` and rejects nil identifiers on construction. This allows to use
+/// `Option<NonNilDebugId>` to represent modules without debug identifier, which is the same size
+/// as `NonNilDebugId` itself:
+///
+/// ```
+/// use std::mem::size_of;
+/// use debugid::{DebugId, NonNilDebugId};
+///
+/// assert!(NonNilDebugId::new(DebugId::nil()).is_none());
+/// assert_eq!(size_of::<Option<NonNilDebugId>>(), size_of::<NonNilDebugId>());
+/// ```
+///
+/// Contrary to `DebugId`, the in-memory representation of this type is not stable.
+///
+/// [`DebugId`]: struct.DebugId.html
+// The fields are declared in the same order as for `DebugId`, so that the derived ordering is
+// consistent with the documented ordering of `DebugId`.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+pub struct NonNilDebugId {
+    bytes: Bytes,
+    appendix: u32,
+    /// The `typ` of the `DebugId` plus one.
+    typ: NonZeroU8,
+}
+
+impl NonNilDebugId {
+    /// Creates a `NonNilDebugId` if the given identifier is not nil.
+    pub fn new(debug_id: DebugId) -> Option<Self> {
+        if debug_id.is_nil() {
+            return None;
+        }
+
+        Some(NonNilDebugId {
+            bytes: debug_id.bytes,
+            appendix: debug_id.appendix,
+            typ: NonZeroU8::new(debug_id.typ + 1)?,
+        })
+    }
+
+    /// Returns the contained identifier.
+    pub fn get(self) -> DebugId {
+        DebugId {
+            bytes: self.bytes,
+            appendix: self.appendix,
+            _padding: [0; 11],
+            typ: self.typ.get() - 1,
+        }
+    }
+}
+
+impl fmt::Debug for NonNilDebugId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NonNilDebugId({})", self.get())
+    }
+}
+
+impl fmt::Display for NonNilDebugId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl str::FromStr for NonNilDebugId {
+    type Err = ParseDebugIdError;
+
+    fn from_str(string: &str) -> Result<Self, ParseDebugIdError> {
+        Self::new(string.parse()?).ok_or_else(|| {
+            ParseDebugIdError::new(ParseErrorKind::InvalidFormat, 0).with_input(string)
+        })
+    }
+}
+
+impl From<NonNilDebugId> for DebugId {
+    fn from(debug_id: NonNilDebugId) -> Self {
+        debug_id.get()
+    }
+}
+
+/// Indicates an error parsing a [`CodeId`](struct.CodeId.html).
+///
+/// The error records the [`ParseErrorKind`] and the byte offset in the input at which parsing
+/// failed. Errors returned from parsing strings also record the beginning of the rejected input,
+/// which is included in the error message.
+///
+/// [`ParseErrorKind`]: enum.ParseErrorKind.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseCodeIdError {
+    kind: ParseErrorKind,
+    offset: usize,
+    input: Option<ErrorInput>,
+}
+
+impl ParseCodeIdError {
+    fn new(kind: ParseErrorKind, offset: usize) -> Self {
+        ParseCodeIdError {
+            kind,
+            offset,
+            input: None,
+        }
+    }
+
+    fn with_input(mut self, input: &str) -> Self {
+        if !input.is_empty() {
+            self.input = Some(ErrorInput::new(input));
+        }
+        self
+    }
+
+    /// Returns the reason why parsing failed.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset in the input at which parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the beginning of the rejected input.
+    ///
+    /// This is `None` if the input was empty or not a string. Long inputs are truncated, see
+    /// [`is_input_truncated`](#method.is_input_truncated).
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_ref().map(|input| input.buf.as_str())
+    }
+
+    /// Returns whether [`input`](#method.input) was truncated.
+    pub fn is_input_truncated(&self) -> bool {
+        self.input.map_or(false, |input| input.truncated)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ParseCodeIdError {}
+
+impl fmt::Display for ParseCodeIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_parse_error(
+            f,
+            "code identifier",
+            self.kind,
+            self.offset,
+            self.input.as_ref(),
+        )
+    }
+}
+
+/// Checks that the string is a non-empty sequence of at most `max_len` hex digits.
+fn check_hex(string: &str, max_len: usize) -> Result<(), ParseCodeIdError> {
+    if string.is_empty() {
+        return Err(ParseCodeIdError::new(ParseErrorKind::Empty, 0));
+    }
+
+    if let Some(offset) = string.bytes().position(|c| !c.is_ascii_hexdigit()) {
+        let kind = invalid_hex_kind(string.as_bytes()[offset]);
+        return Err(ParseCodeIdError::new(kind, offset));
+    }
+
+    if string.len() > max_len {
+        return Err(ParseCodeIdError::new(
+            ParseErrorKind::InvalidLength,
+            max_len,
+        ));
+    }
+
+    Ok(())
+}
+
+/// The kind of a [`CodeId`], which determines its structure.
+///
+/// [`CodeId`]: struct.CodeId.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum CodeIdKind {
+    /// Timestamp and size of image of a Windows PE file.
+    PeTimestampSize,
+    /// Contents of the GNU build id note of an ELF file.
+    GnuBuildId,
+    /// The `LC_UUID` of a Mach binary.
+    MachUuid,
+    /// Contents of the `build_id` custom section of a WebAssembly module.
+    WasmBuildId,
+    /// The build id of a Go binary, hex-encoded.
+    ///
+    /// See [`CodeId::from_go_build_id`](struct.CodeId.html#method.from_go_build_id).
+    GoBuildId,
+    /// The CRC-32 checksum from the `.gnu_debuglink` section of an ELF file.
+    ///
+    /// See [`CodeId::from_debuglink_crc`](struct.CodeId.html#method.from_debuglink_crc).
+    DebugLinkCrc,
+    /// A code identifier with unknown structure.
+    Opaque,
+    /// A code identifier that is not hex-encoded, such as the identifiers of Android split APKs.
+    ///
+    /// See [`CodeId::from_text`](struct.CodeId.html#method.from_text).
+    Text,
+}
+
+impl CodeIdKind {
+    /// Returns whether identifiers of this kind encode a sequence of bytes.
+    #[cfg(feature = "std")]
+    fn is_byte_oriented(self) -> bool {
+        match self {
+            CodeIdKind::GnuBuildId
+            | CodeIdKind::MachUuid
+            | CodeIdKind::WasmBuildId
+            | CodeIdKind::GoBuildId
+            | CodeIdKind::DebugLinkCrc => true,
+            CodeIdKind::PeTimestampSize | CodeIdKind::Opaque | CodeIdKind::Text => false,
+        }
+    }
+}
+
+impl Default for CodeIdKind {
+    fn default() -> Self {
+        CodeIdKind::Opaque
+    }
+}
+
+/// Unique platform-dependent identifier of code files.
+///
+/// This identifier assumes a string representation that depends on the platform and compiler used.
+/// The representation only retains hex characters and canonically stores lower case.
+///
+/// There are the following known formats:
+///
+///  - **MachO UUID**: The unique identifier of a Mach binary, specified in the `LC_UUID` load
+///    command header.
+///  - **GNU Build ID**: Contents of the `.gnu.build-id` note or section contents formatted as
+///    lowercase hex string.
+///  - **PE Timestamp**: Timestamp and size of image values from a Windows PE header. The size of
+///    image value is truncated, so the length of the `CodeId` might not be a multiple of 2.
+///
+/// Constructors for a specific format record the [`CodeIdKind`], which can be queried with
+/// [`CodeId::kind`]. The kind is metadata only: it is not considered when comparing or hashing
+/// code identifiers. Binary serde formats store the kind, while human-readable formats only store
+/// the string representation.
+///
+/// [`CodeIdKind`]: enum.CodeIdKind.html
+/// [`CodeId::kind`]: struct.CodeId.html#method.kind
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+pub struct CodeId {
+    inner: CodeIdBuf,
+    kind: CodeIdKind,
+}
+
+#[cfg(feature = "std")]
+impl CodeId {
+    /// Constructs an empty code identifier.
+    pub fn nil() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a `CodeId` from its string representation.
+    pub fn new(mut string: String) -> Self {
+        string.retain(|c| c.is_ascii_hexdigit());
+        string.make_ascii_lowercase();
+        CodeId {
+            inner: string.into(),
+            kind: CodeIdKind::Opaque,
+        }
+    }
+
+    /// Constructs a `CodeId` that preserves the exact string representation.
+    ///
+    /// Contrary to [`CodeId::new`], the string is neither converted to lowercase nor are any
+    /// characters removed. This allows identifiers to round-trip byte-for-byte, for instance
+    /// when interacting with case-sensitive tooling.
+    ///
+    /// Since code identifiers are compared by their string representation, a preserved
+    /// identifier is not equal to the same identifier constructed via `CodeId::new` if it
+    /// contains uppercase characters.
+    ///
+    /// [`CodeId::new`]: struct.CodeId.html#method.new
+    pub fn new_preserving(string: String) -> Self {
+        CodeId {
+            inner: string.into(),
+            kind: CodeIdKind::Opaque,
+        }
+    }
+
+    /// The maximum number of hex digits accepted by [`CodeId::parse_hex`].
+    ///
+    /// This is sufficient for 64-byte identifiers, such as SHA-512 hashes.
+    ///
+    /// [`CodeId::parse_hex`]: struct.CodeId.html#method.parse_hex
+    pub const MAX_HEX_LEN: usize = 128;
+
+    /// Constructs a `CodeId` from an identifier that is not hex-encoded.
+    ///
+    /// Some ecosystems use code identifiers that consist of arbitrary characters. Passing them to
+    /// [`CodeId::new`] would strip all characters that are not hex digits. Instead, this stores
+    /// the string verbatim and records [`CodeIdKind::Text`]. The string must be non-empty and
+    /// consist of printable ASCII characters other than space.
+    ///
+    /// Human-readable serde formats deserialize strings that are not hex-encoded into text
+    /// identifiers, so they round-trip unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::{CodeId, CodeIdKind};
+    ///
+    /// let code_id = CodeId::from_text("base.apk:Xk3_Lm-9").unwrap();
+    /// assert_eq!(code_id.as_str(), "base.apk:Xk3_Lm-9");
+    /// assert_eq!(code_id.kind(), CodeIdKind::Text);
+    /// ```
+    ///
+    /// [`CodeId::new`]: struct.CodeId.html#method.new
+    /// [`CodeIdKind::Text`]: enum.CodeIdKind.html#variant.Text
+    pub fn from_text(string: &str) -> Result<Self, ParseCodeIdError> {
+        if string.is_empty() {
+            return Err(ParseCodeIdError::new(ParseErrorKind::Empty, 0));
+        }
+
+        if let Some(offset) = string.bytes().position(|c| !c.is_ascii_graphic()) {
+            let kind = match string.as_bytes()[offset].is_ascii() {
+                true => ParseErrorKind::InvalidFormat,
+                false => ParseErrorKind::NonAscii,
+            };
+            return Err(ParseCodeIdError::new(kind, offset).with_input(string));
+        }
+
+        let mut inner = CodeIdBuf::default();
+        inner.write_str(string).expect("");
+
+        Ok(CodeId {
+            inner,
+            kind: CodeIdKind::Text,
+        })
+    }
+
+    /// Parses a `CodeId` from a hex string, rejecting invalid input.
+    ///
+    /// Contrary to [`CodeId::new`], which silently removes all characters that are not hex
+    /// digits, this returns an error if the string is empty, contains any other character, or
+    /// is longer than [`CodeId::MAX_HEX_LEN`]. Uppercase hex digits are accepted and converted
+    /// to lowercase.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::{CodeId, ParseErrorKind};
+    ///
+    /// let error = CodeId::parse_hex("dfb8e43a-f242").unwrap_err();
+    /// assert_eq!(error.kind(), ParseErrorKind::InvalidHex);
+    /// assert_eq!(error.offset(), 8);
+    /// ```
+    ///
+    /// [`CodeId::new`]: struct.CodeId.html#method.new
+    /// [`CodeId::MAX_HEX_LEN`]: struct.CodeId.html#associatedconstant.MAX_HEX_LEN
+    pub fn parse_hex(string: &str) -> Result<Self, ParseCodeIdError> {
+        check_hex(string, Self::MAX_HEX_LEN).map_err(|error| error.with_input(string))?;
+
+        let mut inner = CodeIdBuf::default();
+        inner.write_str(string).expect("");
+        inner.make_ascii_lowercase();
+
+        Ok(CodeId {
+            inner,
+            kind: CodeIdKind::Opaque,
+        })
+    }
+
+    /// Checks whether a string is accepted by [`CodeId::parse_hex`].
+    ///
+    /// This validates the string without constructing a `CodeId`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// assert!(CodeId::is_valid_hex("dfb8e43af2423d73a453aeb6a777ef75"));
+    /// assert!(!CodeId::is_valid_hex("dfb8e43a-f242"));
+    /// ```
+    ///
+    /// [`CodeId::parse_hex`]: struct.CodeId.html#method.parse_hex
+    pub fn is_valid_hex(string: &str) -> bool {
+        check_hex(string, Self::MAX_HEX_LEN).is_ok()
+    }
+
+    /// Parses a GNU build id from a line of `readelf`, `file` or `eu-unstrip` output.
+    ///
+    /// The following formats are recognized:
+    ///
+    ///  - `readelf -n` and `eu-readelf -n`: `    Build ID: 3ae4b8df42f2733d...`
+    ///  - `file`: `ELF 64-bit LSB shared object, ..., BuildID[sha1]=3ae4b8df42f2733d..., stripped`
+    ///  - `eu-unstrip -n`: `0x400000+0x1000 3ae4b8df42f2733d...@0x400284 /usr/bin/foo - -`
+    ///
+    /// The build id must be hex-encoded and is returned with [`CodeIdKind::GnuBuildId`]. Offsets
+    /// in errors refer to the full line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let line = "    Build ID: 3ae4b8df42f2733da453aeb6a777ef7593a66d4f";
+    /// let code_id = CodeId::parse_build_id_line(line).unwrap();
+    /// assert_eq!(code_id.as_str(), "3ae4b8df42f2733da453aeb6a777ef7593a66d4f");
+    /// ```
+    ///
+    /// [`CodeIdKind::GnuBuildId`]: enum.CodeIdKind.html#variant.GnuBuildId
+    pub fn parse_build_id_line(line: &str) -> Result<Self, ParseCodeIdError> {
+        const READELF_LABEL: &str = "Build ID:";
+        const FILE_LABEL: &str = "BuildID[";
+
+        let error = |kind, offset| Err(ParseCodeIdError::new(kind, offset).with_input(line));
+
+        let content = line.trim_start();
+        let start = if let Some(index) = line.find(READELF_LABEL) {
+            index + READELF_LABEL.len()
+        } else if let Some(index) = line.find(FILE_LABEL) {
+            match line[index..].find("]=") {
+                Some(end) => index + end + 2,
+                None => return error(ParseErrorKind::InvalidFormat, index),
+            }
+        } else if content.starts_with("0x") {
+            // Skip the address range of the module.
+            let content_offset = line.len() - content.len();
+            let range_len = content.find(char::is_whitespace).unwrap_or(content.len());
+            content_offset + range_len
+        } else if content.is_empty() {
+            return error(ParseErrorKind::Empty, 0);
+        } else {
+            return error(ParseErrorKind::InvalidFormat, 0);
+        };
+
+        let rest = &line[start..];
+        let offset = start + rest.len() - rest.trim_start().len();
+        let build_id = &line[offset..];
+        let len = build_id
+            .find(|c: char| c.is_whitespace() || c == ',' || c == '@')
+            .unwrap_or(build_id.len());
+
+        match Self::parse_hex(&build_id[..len]) {
+            Ok(code_id) => Ok(code_id.with_kind(CodeIdKind::GnuBuildId)),
+            Err(e) => error(e.kind(), offset + e.offset()),
+        }
+    }
+
+    /// Parses a sequence of hex strings into `CodeId`s.
+    ///
+    /// This returns an iterator that parses each string with [`CodeId::parse_hex`] and yields one
+    /// result per input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let index = "dfb8e43af2423d73a453aeb6a777ef75\nxyz";
+    /// let results: Vec<_> = CodeId::parse_many(index.lines()).collect();
+    /// assert!(results[0].is_ok());
+    /// assert!(results[1].is_err());
+    /// ```
+    ///
+    /// [`CodeId::parse_hex`]: struct.CodeId.html#method.parse_hex
+    pub fn parse_many<'a, I>(lines: I) -> impl Iterator<Item = Result<Self, ParseCodeIdError>> + 'a
+    where
+        I: IntoIterator<Item = &'a str>,
+        I::IntoIter: 'a,
+    {
+        lines.into_iter().map(Self::parse_hex)
+    }
+
+    /// Parses a `CodeId` from a hex string in ASCII bytes, rejecting invalid input.
+    ///
+    /// This applies the same rules as [`CodeId::parse_hex`], but operates on a byte slice, such
+    /// as a line of a memory-mapped symbol index.
+    ///
+    /// [`CodeId::parse_hex`]: struct.CodeId.html#method.parse_hex
+    pub fn from_ascii(bytes: &[u8]) -> Result<Self, ParseCodeIdError> {
+        match str::from_utf8(bytes) {
+            Ok(string) => Self::parse_hex(string),
+            Err(error) => Err(ParseCodeIdError::new(
+                ParseErrorKind::NonAscii,
+                error.valid_up_to(),
+            )),
+        }
+    }
+
+    /// Constructs a `CodeId` from a binary slice.
+    pub fn from_binary(slice: &[u8]) -> Self {
+        let mut inner = CodeIdBuf::default();
+
+        for byte in slice {
+            write!(&mut inner, "{:02x}", byte).expect("");
+        }
+
+        CodeId {
+            inner,
+            kind: CodeIdKind::Opaque,
+        }
+    }
+
+    /// Constructs a `CodeId` from the `LC_UUID` of a Mach binary.
+    ///
+    /// The UUID is formatted as 32 lowercase hex digits in its original byte order. Contrary to
+    /// the GUIDs of PDB files, no byte swapping occurs. The corresponding debug identifier can be
+    /// constructed with [`DebugId::from_uuid`].
+    ///
+    /// [`DebugId::from_uuid`]: struct.DebugId.html#method.from_uuid
+    pub fn from_mach_uuid(uuid: Uuid) -> Self {
+        let mut inner = CodeIdBuf::default();
+        write!(&mut inner, "{:x}", uuid.simple()).expect("");
+
+        CodeId {
+            inner,
+            kind: CodeIdKind::MachUuid,
+        }
+    }
+
+    /// Constructs a `CodeId` from the contents of the `build_id` custom section of a WebAssembly
+    /// module.
+    ///
+    /// The section is specified by the WebAssembly tool conventions and contains the raw bytes
+    /// of the build id.
+    pub fn from_wasm_build_id(build_id: &[u8]) -> Self {
+        Self::from_binary(build_id).with_kind(CodeIdKind::WasmBuildId)
+    }
+
+    /// Parses the GNU build id from the raw contents of an ELF note section.
+    ///
+    /// The data is expected to contain a sequence of ELF notes, such as the contents of the
+    /// `.note.gnu.build-id` section or a `PT_NOTE` segment. The first note of type
+    /// `NT_GNU_BUILD_ID` with name `GNU` is returned. Both little-endian and big-endian notes are
+    /// supported.
+    pub fn from_elf_note(data: &[u8]) -> Result<Self, ParseCodeIdError> {
+        let build_id = find_gnu_build_id(data, read_u32_le)
+            .or_else(|| find_gnu_build_id(data, read_u32_be))
+            .ok_or_else(|| ParseCodeIdError::new(ParseErrorKind::InvalidFormat, 0))?;
+
+        Ok(Self::from_binary(build_id).with_kind(CodeIdKind::GnuBuildId))
+    }
+
+    /// Constructs a `CodeId` from the build id of a Go binary.
+    ///
+    /// Go build ids, as printed by `go tool buildid`, consist of several base64-like hashes
+    /// separated by slashes. Since they are not hex-encoded, this encodes the ASCII bytes of the
+    /// build id as hex and records [`CodeIdKind::GoBuildId`]. The original build id can be
+    /// recovered with [`CodeId::go_build_id`].
+    ///
+    /// This returns an error if the build id is empty or contains characters other than
+    /// printable ASCII.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let code_id = CodeId::from_go_build_id("Xk3_Lm/9bQ-z").unwrap();
+    /// assert_eq!(code_id.as_str(), "586b335f4c6d2f3962512d7a");
+    /// assert_eq!(code_id.go_build_id().as_deref(), Some("Xk3_Lm/9bQ-z"));
+    /// ```
+    ///
+    /// [`CodeIdKind::GoBuildId`]: enum.CodeIdKind.html#variant.GoBuildId
+    /// [`CodeId::go_build_id`]: struct.CodeId.html#method.go_build_id
+    pub fn from_go_build_id(build_id: &str) -> Result<Self, ParseCodeIdError> {
+        if build_id.is_empty() {
+            return Err(ParseCodeIdError::new(ParseErrorKind::Empty, 0));
+        }
+
+        if let Some(offset) = build_id.bytes().position(|c| !c.is_ascii_graphic()) {
+            let kind = match build_id.as_bytes()[offset].is_ascii() {
+                true => ParseErrorKind::InvalidFormat,
+                false => ParseErrorKind::NonAscii,
+            };
+            return Err(ParseCodeIdError::new(kind, offset).with_input(build_id));
+        }
+
+        Ok(Self::from_binary(build_id.as_bytes()).with_kind(CodeIdKind::GoBuildId))
+    }
+
+    /// Returns the original Go build id of a code identifier of kind [`CodeIdKind::GoBuildId`].
+    ///
+    /// This returns `None` for all other kinds, and if the identifier does not decode to
+    /// printable ASCII.
+    ///
+    /// [`CodeIdKind::GoBuildId`]: enum.CodeIdKind.html#variant.GoBuildId
+    pub fn go_build_id(&self) -> Option<String> {
+        if self.kind != CodeIdKind::GoBuildId {
+            return None;
+        }
+
+        let bytes = self.bytes().ok()?;
+        if bytes.is_empty() || !bytes.iter().all(u8::is_ascii_graphic) {
+            return None;
+        }
+
+        String::from_utf8(bytes).ok()
+    }
+
+    /// Constructs a `CodeId` from the CRC-32 checksum of a `.gnu_debuglink` section.
+    ///
+    /// ELF binaries without a GNU build id may still reference their debug file via the
+    /// `.gnu_debuglink` section, which contains the file name and the CRC-32 checksum of the
+    /// debug file. The checksum is a much weaker identifier than a build id, but it is the only
+    /// one available for such binaries. It is formatted as eight hex digits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let code_id = CodeId::from_debuglink_crc(0x0a1b_2c3d);
+    /// assert_eq!(code_id.as_str(), "0a1b2c3d");
+    /// assert_eq!(code_id.debuglink_crc(), Some(0x0a1b_2c3d));
+    /// ```
+    pub fn from_debuglink_crc(crc: u32) -> Self {
+        let mut inner = CodeIdBuf::default();
+        write!(&mut inner, "{:08x}", crc).expect("");
+
+        CodeId {
+            inner,
+            kind: CodeIdKind::DebugLinkCrc,
+        }
+    }
+
+    /// Constructs a `CodeId` from the `TimeDateStamp` and `SizeOfImage` of a PE header.
+    ///
+    /// This is the key used by Microsoft symbol servers to look up executables. The timestamp is
+    /// formatted as eight hex digits, followed by the size of image without leading zeros.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    /// assert_eq!(code_id.as_str(), "5ccc38584b08000");
+    /// ```
+    pub fn from_pe(timestamp: u32, size_of_image: u32) -> Self {
+        let mut inner = CodeIdBuf::default();
+        write!(&mut inner, "{:08x}{:x}", timestamp, size_of_image).expect("");
+
+        CodeId {
+            inner,
+            kind: CodeIdKind::PeTimestampSize,
+        }
+    }
+
+    /// Splits the string representation of a PE code identifier into its `TimeDateStamp` and
+    /// `SizeOfImage`.
+    ///
+    /// The string must consist of the timestamp as eight hex digits, followed by one to eight hex
+    /// digits of the size of image, as produced by [`CodeId::from_pe`]. This is the inverse of
+    /// `CodeId::from_pe`, and is useful to recover the header values from symbol store keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// assert_eq!(CodeId::parse_pe("5eb8679a17b000"), Ok((0x5eb8_679a, 0x0017_b000)));
+    /// assert!(CodeId::parse_pe("5eb8679a").is_err());
+    /// ```
+    ///
+    /// [`CodeId::from_pe`]: struct.CodeId.html#method.from_pe
+    pub fn parse_pe(string: &str) -> Result<(u32, u32), ParseCodeIdError> {
+        check_hex(string, 16).map_err(|error| error.with_input(string))?;
+        if string.len() < 9 {
+            return Err(
+                ParseCodeIdError::new(ParseErrorKind::InvalidLength, string.len())
+                    .with_input(string),
+            );
+        }
+
+        // Both parts consist of at most eight hex digits, so they always fit.
+        let timestamp = u32::from_str_radix(&string[..8], 16).unwrap_or_default();
+        let size_of_image = u32::from_str_radix(&string[8..], 16).unwrap_or_default();
+        Ok((timestamp, size_of_image))
+    }
+
+    /// Returns a copy of this identifier with the given kind.
+    ///
+    /// Use this to record the kind of identifiers that were constructed via [`CodeId::new`] or
+    /// [`CodeId::from_binary`]. The string representation is not validated against the kind.
+    ///
+    /// [`CodeId::new`]: struct.CodeId.html#method.new
+    /// [`CodeId::from_binary`]: struct.CodeId.html#method.from_binary
+    pub fn with_kind(mut self, kind: CodeIdKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Returns the kind of this code identifier.
+    ///
+    /// This is [`CodeIdKind::Opaque`] unless the identifier was constructed for a specific
+    /// format.
+    ///
+    /// [`CodeIdKind::Opaque`]: enum.CodeIdKind.html#variant.Opaque
+    pub fn kind(&self) -> CodeIdKind {
+        self.kind
+    }
+
+    /// Returns whether this identifier is nil, i.e. it is empty.
+    pub fn is_nil(&self) -> bool {
+        self.as_str().is_empty()
+    }
+
+    /// Returns the number of hex digits in this identifier.
+    ///
+    /// This is the length of the string representation. For identifiers of kind
+    /// [`CodeIdKind::Text`], this counts characters rather than hex digits.
+    ///
+    /// [`CodeIdKind::Text`]: enum.CodeIdKind.html#variant.Text
+    pub fn nibble_count(&self) -> usize {
+        self.as_str().len()
+    }
+
+    /// Returns the number of whole bytes encoded by this identifier.
+    ///
+    /// A trailing odd hex digit, as in some PE code identifiers, is not counted. Use this to
+    /// judge the strength of an identifier, for example to warn about build ids shorter than
+    /// eight bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// assert_eq!(CodeId::from_binary(&[0xab; 20]).len_bytes(), 20);
+    /// assert_eq!(CodeId::from_pe(0x5ccc_3858, 0x04b0_8000).len_bytes(), 7);
+    /// ```
+    pub fn len_bytes(&self) -> usize {
+        self.nibble_count() / 2
+    }
+
+    /// Compares two identifiers, ignoring trailing zero bytes and case.
+    ///
+    /// Some producers pad short build ids with zeros to 16 bytes, while others do not. This
+    /// considers such identifiers equal, for example `dfb8e43af2423d73` and
+    /// `dfb8e43af2423d730000000000000000`. Identifiers with an odd number of hex digits, such as
+    /// PE code identifiers, are never padded and only compare equal if they are identical.
+    pub fn eq_normalized(&self, other: &CodeId) -> bool {
+        fn trim_zero_bytes(mut hex: &str) -> &str {
+            if hex.len() % 2 == 0 {
+                while hex.ends_with("00") {
+                    hex = &hex[..hex.len() - 2];
+                }
+            }
+            hex
+        }
+
+        trim_zero_bytes(self.as_str()).eq_ignore_ascii_case(trim_zero_bytes(other.as_str()))
+    }
+
+    /// Derives the debug identifier from this code identifier of a file in the given format.
+    ///
+    /// See [`DebugId::try_from_code_id`] for the rules per file format. Debug identifiers of PE
+    /// files cannot be derived from their code identifier, so this always returns an error for
+    /// [`FileFormat::Pe`].
+    ///
+    /// [`DebugId::try_from_code_id`]: struct.DebugId.html#method.try_from_code_id
+    /// [`FileFormat::Pe`]: enum.FileFormat.html#variant.Pe
+    pub fn to_debug_id(&self, format: FileFormat) -> Result<DebugId, ParseDebugIdError> {
+        DebugId::try_from_code_id(self, format)
+    }
+
+    /// Checks that this identifier is plausible for a file of the given format.
+    ///
+    /// All identifiers must be non-empty and consist of hex digits only. Additionally, this
+    /// checks the following length constraints:
+    ///
+    ///  - **ELF**: At least 8 bytes, typically 20 bytes for SHA-1 build ids.
+    ///  - **MachO**: Exactly 16 bytes.
+    ///  - **PE**: Between 9 and 16 hex digits.
+    ///  - **WASM**: At least one byte.
+    ///
+    /// Except for PE, identifiers must consist of whole bytes, i.e. an even number of hex digits.
+    pub fn validate_for(&self, format: FileFormat) -> Result<(), ParseCodeIdError> {
+        let hex = self.as_str();
+        check_hex(hex, usize::MAX).map_err(|error| error.with_input(hex))?;
+
+        let len = hex.len();
+        let valid = match format {
+            FileFormat::Elf => len >= 16 && len % 2 == 0,
+            FileFormat::MachO => len == 32,
+            FileFormat::Pe => (9..=16).contains(&len),
+            FileFormat::Wasm => len % 2 == 0,
+        };
+
+        match valid {
+            true => Ok(()),
+            false => Err(ParseCodeIdError::new(ParseErrorKind::InvalidLength, len).with_input(hex)),
+        }
+    }
+
+    /// Decodes the hex representation of this identifier into raw bytes.
+    ///
+    /// This returns an error if the identifier contains characters other than hex digits, which
+    /// is only possible for identifiers constructed via [`CodeId::new_preserving`]. It also
+    /// returns an error for identifiers with an odd number of hex digits, such as some PE code
+    /// identifiers, since they do not correspond to a sequence of bytes. Use
+    /// [`CodeId::timestamp`] and [`CodeId::size_of_image`] to decode PE code identifiers.
+    ///
+    /// [`CodeId::new_preserving`]: struct.CodeId.html#method.new_preserving
+    /// [`CodeId::timestamp`]: struct.CodeId.html#method.timestamp
+    /// [`CodeId::size_of_image`]: struct.CodeId.html#method.size_of_image
+    pub fn bytes(&self) -> Result<Vec<u8>, ParseCodeIdError> {
+        let hex = self.as_str().as_bytes();
+        if let Some(offset) = hex.iter().position(|c| !c.is_ascii_hexdigit()) {
+            let kind = invalid_hex_kind(hex[offset]);
+            return Err(ParseCodeIdError::new(kind, offset));
+        }
+
+        if hex.len() % 2 != 0 {
+            return Err(ParseCodeIdError::new(
+                ParseErrorKind::InvalidLength,
+                hex.len(),
+            ));
+        }
+
+        Ok(decode_hex(hex).flatten().collect())
+    }
+
+    /// Returns a borrowed view of the first `n_bytes` bytes of this identifier.
+    ///
+    /// This covers `2 * n_bytes` hex digits, or the entire identifier if it is shorter. Use this
+    /// to compare long build ids against shorter prefixes without allocating, for instance the
+    /// first 16 bytes of a SHA-1 GNU build id that are used to derive its debug identifier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let build_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75c8f1e4a3".into());
+    /// let prefix = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    /// assert_eq!(build_id.truncated(16), prefix);
+    /// ```
+    pub fn truncated(&self, n_bytes: usize) -> CodeIdRef<'_> {
+        let hex = self.as_str();
+        let len = n_bytes.saturating_mul(2).min(hex.len());
+        CodeIdRef {
+            inner: hex.get(..len).unwrap_or(hex),
+        }
+    }
+
+    /// Returns a wrapper which when formatted via `fmt::Display` will format this identifier in
+    /// uppercase.
+    ///
+    /// Microsoft symbol servers conventionally use uppercase PE code identifiers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    /// assert_eq!(code_id.uppercase().to_string(), "5CCC38584B08000");
+    /// ```
+    pub fn uppercase(&self) -> UppercaseFormat<'_> {
+        UppercaseFormat { inner: self }
+    }
+
+    /// Returns a wrapper which when formatted via `fmt::Display` will pad this identifier to
+    /// whole bytes.
+    ///
+    /// If the kind of this identifier encodes a sequence of bytes, such as
+    /// [`CodeIdKind::GnuBuildId`], an odd number of hex digits is padded with a leading zero.
+    /// Such identifiers usually result from formatting a number without leading zeros. PE, opaque
+    /// and text identifiers are formatted unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::{CodeId, CodeIdKind};
+    ///
+    /// let code_id = CodeId::new("b8e43af2423d73a".into()).with_kind(CodeIdKind::GnuBuildId);
+    /// assert_eq!(code_id.padded().to_string(), "0b8e43af2423d73a");
+    ///
+    /// let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    /// assert_eq!(code_id.padded().to_string(), "5ccc38584b08000");
+    /// ```
+    ///
+    /// [`CodeIdKind::GnuBuildId`]: enum.CodeIdKind.html#variant.GnuBuildId
+    pub fn padded(&self) -> PaddedFormat<'_> {
+        PaddedFormat { inner: self }
+    }
+
+    /// Returns a wrapper which when formatted via `fmt::Display` will split this identifier into
+    /// groups of `group_len` characters, separated by `separator`.
+    ///
+    /// This is intended for diagnostics output, where long build ids are hard to read. A
+    /// `group_len` of zero disables grouping.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let code_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75c8f1e4a3".into());
+    /// assert_eq!(
+    ///     code_id.chunked(8, ' ').to_string(),
+    ///     "dfb8e43a f2423d73 a453aeb6 a777ef75 c8f1e4a3"
+    /// );
+    /// ```
+    pub fn chunked(&self, group_len: usize, separator: char) -> ChunkedFormat<'_> {
+        ChunkedFormat {
+            inner: self,
+            group_len,
+            separator,
+        }
+    }
+
+    /// Returns the `TimeDateStamp` of a PE code identifier.
+    ///
+    /// This returns `None` if the identifier is not a valid PE code identifier, see
+    /// [`CodeId::from_pe`]. Identifiers of kind [`CodeIdKind::Opaque`] are accepted if they have
+    /// the structure of a PE code identifier.
+    ///
+    /// [`CodeId::from_pe`]: struct.CodeId.html#method.from_pe
+    /// [`CodeIdKind::Opaque`]: enum.CodeIdKind.html#variant.Opaque
+    pub fn timestamp(&self) -> Option<u32> {
+        self.pe_parts().map(|(timestamp, _)| timestamp)
+    }
+
+    /// Returns the `SizeOfImage` of a PE code identifier.
+    ///
+    /// This returns `None` if the identifier is not a valid PE code identifier, see
+    /// [`CodeId::from_pe`]. Identifiers of kind [`CodeIdKind::Opaque`] are accepted if they have
+    /// the structure of a PE code identifier.
+    ///
+    /// [`CodeId::from_pe`]: struct.CodeId.html#method.from_pe
+    /// [`CodeIdKind::Opaque`]: enum.CodeIdKind.html#variant.Opaque
+    pub fn size_of_image(&self) -> Option<u32> {
+        self.pe_parts().map(|(_, size_of_image)| size_of_image)
+    }
+
+    /// Returns the UUID of a Mach code identifier.
+    ///
+    /// This returns `None` unless the identifier consists of exactly 16 bytes, as specified in
+    /// the `LC_UUID` load command. Identifiers of kind [`CodeIdKind::Opaque`] are accepted if
+    /// they have the right length.
+    ///
+    /// [`CodeIdKind::Opaque`]: enum.CodeIdKind.html#variant.Opaque
+    pub fn uuid(&self) -> Option<Uuid> {
+        match self.kind {
+            CodeIdKind::MachUuid | CodeIdKind::Opaque if self.as_str().len() == 32 => {
+                Uuid::parse_str(self.as_str()).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a key that orders code identifiers by kind, then length, then lexicographically.
+    ///
+    /// The default ordering of `CodeId` is lexicographic on the string representation, which
+    /// interleaves identifiers of different formats. Sorting by this key groups identifiers of
+    /// the same [`CodeIdKind`] and places shorter identifiers first, so that PE code identifiers,
+    /// Mach UUIDs and GNU build ids are listed separately. Kinds are ordered as they are declared
+    /// in `CodeIdKind`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let mut ids = vec![
+    ///     CodeId::from_binary(&[0xab; 20]),
+    ///     CodeId::from_pe(0xffff_ffff, 0x1000),
+    ///     CodeId::from_binary(&[0x01; 16]),
+    /// ];
+    ///
+    /// ids.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    /// assert_eq!(ids[0].as_str(), "ffffffff1000");
+    /// assert_eq!(ids[1].as_str(), "01".repeat(16));
+    /// ```
+    ///
+    /// [`CodeIdKind`]: enum.CodeIdKind.html
+    pub fn sort_key(&self) -> impl Ord + '_ {
+        (self.kind, self.as_str().len(), self.as_str())
+    }
+
+    /// Returns the `.gnu_debuglink` checksum of a code identifier of kind
+    /// [`CodeIdKind::DebugLinkCrc`].
+    ///
+    /// This returns `None` for all other kinds, since a checksum cannot be distinguished from
+    /// other identifiers of the same length.
+    ///
+    /// [`CodeIdKind::DebugLinkCrc`]: enum.CodeIdKind.html#variant.DebugLinkCrc
+    pub fn debuglink_crc(&self) -> Option<u32> {
+        match self.kind {
+            CodeIdKind::DebugLinkCrc if self.as_str().len() == 8 => {
+                u32::from_str_radix(self.as_str(), 16).ok()
+            }
+            _ => None,
+        }
+    }
+
+    fn pe_parts(&self) -> Option<(u32, u32)> {
+        match self.kind {
+            CodeIdKind::PeTimestampSize | CodeIdKind::Opaque => Self::parse_pe(self.as_str()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the string representation of this code identifier.
+    pub fn as_str(&self) -> &str {
+        self.inner.as_str()
+    }
+
+    /// Returns the first `len` characters of the string representation.
+    ///
+    /// This is intended for sharded directory layouts and object store prefixes. If `len` exceeds
+    /// the length of the identifier, the entire identifier is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    /// assert_eq!(code_id.shard_prefix(2), "3a");
+    /// ```
+    pub fn shard_prefix(&self, len: usize) -> &str {
+        let string = self.as_str();
+        match string.char_indices().nth(len) {
+            Some((index, _)) => &string[..index],
+            None => string,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq for CodeId {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for CodeId {}
+
+#[cfg(feature = "std")]
+impl Hash for CodeId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+#[cfg(feature = "std")]
+impl Ord for CodeId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialOrd for CodeId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares the string representation of the identifier to a string.
+///
+/// This is consistent with comparing two `CodeId`s: the string must match exactly, including
+/// case. Contrary to [`CodeId::new`], the string is not normalized, so uppercase strings or
+/// strings containing separators never compare equal to a normalized identifier. Use
+/// [`CodeId::eq_normalized`] to ignore case.
+///
+/// [`CodeId::new`]: struct.CodeId.html#method.new
+/// [`CodeId::eq_normalized`]: struct.CodeId.html#method.eq_normalized
+#[cfg(feature = "std")]
+impl PartialEq<str> for CodeId {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> PartialEq<&'a str> for CodeId {
+    fn eq(&self, other: &&'a str) -> bool {
+        *self == **other
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<CodeId> for str {
+    fn eq(&self, other: &CodeId) -> bool {
+        *other == *self
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<CodeId> for &str {
+    fn eq(&self, other: &CodeId) -> bool {
+        *other == **self
+    }
+}
+
+/// Compares the identifier to raw bytes.
+///
+/// This decodes the hex representation without allocating. The identifier compares equal if it is
+/// equal to [`CodeId::from_binary`] of the bytes, so identifiers with an odd number of hex digits,
+/// uppercase hex digits or characters other than hex digits never compare equal.
+///
+/// [`CodeId::from_binary`]: struct.CodeId.html#method.from_binary
+#[cfg(feature = "std")]
+impl PartialEq<[u8]> for CodeId {
+    fn eq(&self, other: &[u8]) -> bool {
+        let hex = self.as_str().as_bytes();
+        hex.len() == other.len() * 2
+            && !hex.iter().any(u8::is_ascii_uppercase)
+            && decode_hex(hex)
+                .zip(other)
+                .all(|(byte, expected)| byte == Some(*expected))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> PartialEq<&'a [u8]> for CodeId {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        *self == **other
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<CodeId> for [u8] {
+    fn eq(&self, other: &CodeId) -> bool {
+        *other == *self
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for CodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CodeId({})", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<String> for CodeId {
+    fn from(string: String) -> Self {
+        Self::new(string)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<&'_ str> for CodeId {
+    fn from(string: &str) -> Self {
+        Self::new(string.into())
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<str> for CodeId {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl str::FromStr for CodeId {
+    type Err = ParseCodeIdError;
+
+    fn from_str(string: &str) -> Result<Self, ParseCodeIdError> {
+        Ok(Self::new(string.into()))
+    }
+}
+
+/// A borrowed view of a [`CodeId`] or a part of it.
+///
+/// Returned by [`CodeId::truncated`]. This compares equal to a `CodeId` with the same string
+/// representation.
+///
+/// [`CodeId`]: struct.CodeId.html
+/// [`CodeId::truncated`]: struct.CodeId.html#method.truncated
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CodeIdRef<'a> {
+    inner: &'a str,
+}
+
+impl<'a> CodeIdRef<'a> {
+    /// Returns whether this identifier is nil, i.e. it is empty.
+    pub fn is_nil(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the string representation of this code identifier.
+    pub fn as_str(&self) -> &'a str {
+        self.inner
+    }
+
+    /// Converts this view into an owned `CodeId`.
+    #[cfg(feature = "std")]
+    pub fn to_code_id(&self) -> CodeId {
+        let mut inner = CodeIdBuf::default();
+        inner.write_str(self.inner).expect("");
+
+        CodeId {
+            inner,
+            kind: CodeIdKind::Opaque,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> From<&'a CodeId> for CodeIdRef<'a> {
+    fn from(code_id: &'a CodeId) -> Self {
+        CodeIdRef {
+            inner: code_id.as_str(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<CodeId> for CodeIdRef<'_> {
+    fn eq(&self, other: &CodeId) -> bool {
+        self.inner == other.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<CodeIdRef<'_>> for CodeId {
+    fn eq(&self, other: &CodeIdRef<'_>) -> bool {
+        self.as_str() == other.inner
+    }
+}
+
+impl fmt::Display for CodeIdRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.inner)
+    }
+}
+
+impl fmt::Debug for CodeIdRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CodeIdRef({})", self)
+    }
+}
+
+/// A shared [`CodeId`] that is cheap to clone.
+///
+/// Cloning a `CodeId` copies its string representation. When the same identifiers are referenced
+/// many times, for instance the modules of stack frames across many crash reports, this type
+/// shares a single reference-counted allocation instead. It compares, hashes and formats exactly
+/// like the `CodeId` it was created from.
+///
+/// # Example
+///
+/// ```
+/// use debugid::{CodeId, CodeIdArc};
+///
+/// let code_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+/// let shared = CodeIdArc::from(&code_id);
+/// let cloned = shared.clone();
+///
+/// assert_eq!(cloned, code_id);
+/// assert_eq!(cloned.to_code_id(), code_id);
+/// ```
+///
+/// [`CodeId`]: struct.CodeId.html
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct CodeIdArc {
+    inner: Arc<str>,
+    kind: CodeIdKind,
+}
+
+#[cfg(feature = "std")]
+impl CodeIdArc {
+    /// Returns the kind of this code identifier.
+    pub fn kind(&self) -> CodeIdKind {
+        self.kind
+    }
+
+    /// Returns whether this identifier is nil, i.e. it is empty.
+    pub fn is_nil(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the string representation of this code identifier.
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    /// Converts this identifier into an owned `CodeId`.
+    pub fn to_code_id(&self) -> CodeId {
+        let mut inner = CodeIdBuf::default();
+        inner.write_str(&self.inner).expect("");
+
+        CodeId {
+            inner,
+            kind: self.kind,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for CodeIdArc {
+    fn default() -> Self {
+        Self::from(&CodeId::nil())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<&'_ CodeId> for CodeIdArc {
+    fn from(code_id: &CodeId) -> Self {
+        CodeIdArc {
+            inner: code_id.as_str().into(),
+            kind: code_id.kind,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<CodeId> for CodeIdArc {
+    fn from(code_id: CodeId) -> Self {
+        Self::from(&code_id)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<CodeIdArc> for CodeId {
+    fn from(code_id: CodeIdArc) -> Self {
+        code_id.to_code_id()
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq for CodeIdArc {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for CodeIdArc {}
+
+#[cfg(feature = "std")]
+impl PartialEq<CodeId> for CodeIdArc {
+    fn eq(&self, other: &CodeId) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<CodeIdArc> for CodeId {
+    fn eq(&self, other: &CodeIdArc) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Hash for CodeIdArc {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+#[cfg(feature = "std")]
+impl Ord for CodeIdArc {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialOrd for CodeIdArc {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CodeIdArc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for CodeIdArc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CodeIdArc({})", self)
+    }
+}
+
+/// The code identifiers of all architecture slices of a fat binary.
+///
+/// Universal Mach binaries contain multiple slices, each with its own `LC_UUID`, so a single
+/// [`CodeId`] cannot describe the entire file. This maps architecture names, such as `x86_64`
+/// or `arm64`, to the code identifier of the respective slice. Architectures are iterated in
+/// sorted order.
+///
+/// With the `serde` feature, this is serialized as a map from architecture to code identifier.
+///
+/// # Example
+///
+/// ```
+/// use debugid::{CodeId, FatCodeId};
+///
+/// let mut fat = FatCodeId::new();
+/// fat.insert("x86_64", CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into()));
+/// fat.insert("arm64", CodeId::new("2b36e3fc0b5a3e8c9d11c4e6c3d8a0f1".into()));
+///
+/// assert_eq!(fat.len(), 2);
+/// assert_eq!(fat.get("arm64").unwrap().as_str(), "2b36e3fc0b5a3e8c9d11c4e6c3d8a0f1");
+/// ```
+///
+/// [`CodeId`]: struct.CodeId.html
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FatCodeId {
+    slices: BTreeMap<String, CodeId>,
+}
+
+#[cfg(feature = "std")]
+impl FatCodeId {
+    /// Constructs an empty set of code identifiers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the code identifier of the slice with the given architecture.
+    ///
+    /// Returns the previous code identifier of this architecture, if any.
+    pub fn insert<A: Into<String>>(&mut self, arch: A, code_id: CodeId) -> Option<CodeId> {
+        self.slices.insert(arch.into(), code_id)
+    }
+
+    /// Returns the code identifier of the slice with the given architecture.
+    pub fn get(&self, arch: &str) -> Option<&CodeId> {
+        self.slices.get(arch)
+    }
+
+    /// Returns the architecture of the slice with the given code identifier.
+    pub fn arch_of(&self, code_id: &CodeId) -> Option<&str> {
+        self.iter()
+            .find(|(_, slice_id)| *slice_id == code_id)
+            .map(|(arch, _)| arch)
+    }
+
+    /// Returns whether any slice has the given code identifier.
+    pub fn contains(&self, code_id: &CodeId) -> bool {
+        self.arch_of(code_id).is_some()
+    }
+
+    /// Returns the number of slices.
+    pub fn len(&self) -> usize {
+        self.slices.len()
+    }
+
+    /// Returns whether there are no slices.
+    pub fn is_empty(&self) -> bool {
+        self.slices.is_empty()
+    }
+
+    /// Iterates over architectures and code identifiers of all slices.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &CodeId)> {
+        self.slices
+            .iter()
+            .map(|(arch, code_id)| (arch.as_str(), code_id))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: Into<String>> std::iter::FromIterator<(A, CodeId)> for FatCodeId {
+    fn from_iter<I: IntoIterator<Item = (A, CodeId)>>(iter: I) -> Self {
+        FatCodeId {
+            slices: iter
+                .into_iter()
+                .map(|(arch, code_id)| (arch.into(), code_id))
+                .collect(),
+        }
+    }
+}
+
+/// A code identifier with fixed capacity that never allocates.
+///
+/// This type stores up to [`FixedCodeId::CAPACITY`] hex digits inline, which covers all common
+/// code identifiers including SHA-1 GNU build ids. It is intended for code paths that must not
+/// allocate, and formats exactly like [`CodeId`]. Contrary to `CodeId`, constructors validate
+/// their input and never remove characters.
+///
+/// This type is also available on `no_std` targets, by disabling the default `std` feature.
+/// Without it, only `FixedCodeId`, [`DebugId`] and the types they depend on are available.
+///
+/// # Example
+///
+/// ```
+/// use debugid::{CodeId, FixedCodeId};
+///
+/// let fixed = FixedCodeId::from_binary(b"\xdf\xb8\xe4\x3a\xf2\x42\x3d\x73").unwrap();
+/// assert_eq!(fixed.as_str(), "dfb8e43af2423d73");
+/// assert_eq!(CodeId::from(fixed), CodeId::new("dfb8e43af2423d73".into()));
+/// ```
+///
+/// [`CodeId`]: struct.CodeId.html
+/// [`DebugId`]: struct.DebugId.html
+/// [`FixedCodeId::CAPACITY`]: struct.FixedCodeId.html#associatedconstant.CAPACITY
+#[derive(Clone, Copy)]
+pub struct FixedCodeId {
+    inner: StackBuffer,
+}
+
+impl FixedCodeId {
+    /// The maximum number of hex digits that can be stored.
+    pub const CAPACITY: usize = 48;
+
+    /// Constructs an empty code identifier.
+    pub fn nil() -> Self {
+        FixedCodeId {
+            inner: StackBuffer::new(),
+        }
+    }
+
+    /// Parses a `FixedCodeId` from a hex string.
+    ///
+    /// This returns an error if the string is empty, contains characters other than hex digits
+    /// or exceeds the capacity, like [`CodeId::parse_hex`]. Uppercase hex digits are converted to
+    /// lowercase. Use [`FixedCodeId::nil`] to construct an empty identifier.
+    ///
+    /// [`CodeId::parse_hex`]: struct.CodeId.html#method.parse_hex
+    /// [`FixedCodeId::nil`]: struct.FixedCodeId.html#method.nil
+    pub fn new(string: &str) -> Result<Self, ParseCodeIdError> {
+        check_hex(string, Self::CAPACITY).map_err(|error| error.with_input(string))?;
+
+        let mut inner = StackBuffer::new();
+        inner.write_str(string).expect("");
+        inner.bytes[..inner.len].make_ascii_lowercase();
+        Ok(FixedCodeId { inner })
+    }
+
+    /// Constructs a `FixedCodeId` from a binary slice.
+    ///
+    /// This returns an error if the slice is longer than half the capacity.
+    pub fn from_binary(slice: &[u8]) -> Result<Self, ParseCodeIdError> {
+        if slice.len() > Self::CAPACITY / 2 {
+            return Err(ParseCodeIdError::new(
+                ParseErrorKind::InvalidLength,
+                Self::CAPACITY / 2,
+            ));
+        }
+
+        let mut inner = StackBuffer::new();
+
+        for byte in slice {
+            write!(&mut inner, "{:02x}", byte).expect("");
+        }
+
+        Ok(FixedCodeId { inner })
+    }
+
+    /// Returns whether this identifier is nil, i.e. it is empty.
+    pub fn is_nil(&self) -> bool {
+        self.inner.len == 0
+    }
+
+    /// Returns the string representation of this code identifier.
+    pub fn as_str(&self) -> &str {
+        self.inner.as_str()
+    }
+}
+
+impl Default for FixedCodeId {
+    fn default() -> Self {
+        Self::nil()
+    }
+}
+
+impl PartialEq for FixedCodeId {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for FixedCodeId {}
+
+impl Hash for FixedCodeId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl Ord for FixedCodeId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialOrd for FixedCodeId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for FixedCodeId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let uuid = self.uuid();
-        f.debug_struct("DebugId")
-            .field("uuid", &uuid.hyphenated().to_string())
-            .field("appendix", &self.appendix())
-            .finish()
+        f.pad(self.as_str())
     }
 }
 
-impl fmt::Display for DebugId {
+impl fmt::Debug for FixedCodeId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.is_pdb20() {
-            true => {
-                let timestamp = self.timestamp();
-                write!(f, "{:08X}", timestamp)?;
-            }
-            false => {
-                let uuid = self.uuid();
-                uuid.fmt(f)?;
-            }
-        }
-        if self.appendix > 0 {
-            write!(f, "-{:x}", { self.appendix })?;
-        }
-        Ok(())
+        write!(f, "FixedCodeId({})", self)
     }
 }
 
-impl str::FromStr for DebugId {
-    type Err = ParseDebugIdError;
+impl str::FromStr for FixedCodeId {
+    type Err = ParseCodeIdError;
 
-    fn from_str(string: &str) -> Result<Self, ParseDebugIdError> {
-        let options = ParseOptions {
-            allow_hyphens: true,
-            require_appendix: false,
-            allow_tail: true,
-        };
-        Self::parse_str(string, options).ok_or(ParseDebugIdError)
+    fn from_str(string: &str) -> Result<Self, ParseCodeIdError> {
+        Self::new(string)
     }
 }
 
-impl From<Uuid> for DebugId {
-    fn from(uuid: Uuid) -> Self {
-        DebugId::from_uuid(uuid)
+#[cfg(feature = "std")]
+impl From<FixedCodeId> for CodeId {
+    fn from(code_id: FixedCodeId) -> Self {
+        let mut inner = CodeIdBuf::default();
+        // Writing into a `CodeIdBuf` cannot fail.
+        inner.write_str(code_id.as_str()).ok();
+
+        CodeId {
+            inner,
+            kind: CodeIdKind::Opaque,
+        }
     }
 }
 
-impl From<(Uuid, u32)> for DebugId {
-    fn from(tuple: (Uuid, u32)) -> Self {
-        let (uuid, appendix) = tuple;
-        DebugId::from_parts(uuid, appendix)
+#[cfg(feature = "std")]
+impl TryFrom<&'_ CodeId> for FixedCodeId {
+    type Error = ParseCodeIdError;
+
+    fn try_from(code_id: &CodeId) -> Result<Self, ParseCodeIdError> {
+        Self::new(code_id.as_str())
     }
 }
 
-/// Wrapper around [`DebugId`] for Breakpad formatting.
+/// The code identifier of a Windows PE file.
 ///
-/// **Example:**
+/// This consists of the `TimeDateStamp` and `SizeOfImage` fields of the PE header. Contrary to
+/// the lenient parsing of [`CodeId`], `FromStr` validates the input with the rules of
+/// [`CodeId::parse_pe`].
 ///
-/// ```
-/// # extern crate debugid;
-/// use std::str::FromStr;
-/// use debugid::DebugId;
+/// # Example
 ///
-/// # fn foo() -> Result<(), debugid::ParseDebugIdError> {
-/// let id = DebugId::from_breakpad("DFB8E43AF2423D73A453AEB6A777EF75a")?;
-/// assert_eq!("DFB8E43AF2423D73A453AEB6A777EF75a".to_string(), id.breakpad().to_string());
-/// # Ok(())
-/// # }
+/// ```
+/// use debugid::{CodeId, PeCodeId};
 ///
-/// # fn main() { foo().unwrap() }
+/// let pe: PeCodeId = "5CCC38584B08000".parse().unwrap();
+/// assert_eq!(pe.timestamp(), 0x5ccc_3858);
+/// assert_eq!(pe.size_of_image(), 0x04b0_8000);
+/// assert_eq!(CodeId::from(pe).as_str(), "5ccc38584b08000");
 /// ```
 ///
-/// [`DebugId`]: struct.DebugId.html
-#[derive(Debug)]
-pub struct BreakpadFormat<'a> {
-    inner: &'a DebugId,
+/// [`CodeId`]: struct.CodeId.html
+/// [`CodeId::parse_pe`]: struct.CodeId.html#method.parse_pe
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PeCodeId {
+    timestamp: u32,
+    size_of_image: u32,
 }
 
-impl<'a> fmt::Display for BreakpadFormat<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.inner.is_pdb20() {
-            true => {
-                let timestamp = self.inner.timestamp();
-                write!(f, "{:08X}{:x}", timestamp, self.inner.appendix())
-            }
-            false => {
-                let uuid = self.inner.uuid();
-                write!(f, "{:X}{:x}", uuid.simple(), self.inner.appendix())
-            }
+impl PeCodeId {
+    /// Constructs a `PeCodeId` from the `TimeDateStamp` and `SizeOfImage` of a PE header.
+    pub fn new(timestamp: u32, size_of_image: u32) -> Self {
+        PeCodeId {
+            timestamp,
+            size_of_image,
         }
     }
-}
 
-/// Indicates an error parsing a [`CodeId`](struct.CodeId.html).
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct ParseCodeIdError;
+    /// Returns the `TimeDateStamp` of the PE header.
+    pub fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
 
-impl error::Error for ParseCodeIdError {}
+    /// Returns the `SizeOfImage` of the PE header.
+    pub fn size_of_image(&self) -> u32 {
+        self.size_of_image
+    }
+}
 
-impl fmt::Display for ParseCodeIdError {
+impl fmt::Display for PeCodeId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "invalid code identifier")
+        write!(f, "{:08x}{:x}", self.timestamp, self.size_of_image)
     }
 }
 
-/// Unique platform-dependent identifier of code files.
+#[cfg(feature = "std")]
+impl str::FromStr for PeCodeId {
+    type Err = ParseCodeIdError;
+
+    fn from_str(string: &str) -> Result<Self, ParseCodeIdError> {
+        let (timestamp, size_of_image) = CodeId::parse_pe(string)?;
+        Ok(Self::new(timestamp, size_of_image))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<PeCodeId> for CodeId {
+    fn from(code_id: PeCodeId) -> Self {
+        CodeId::from_pe(code_id.timestamp, code_id.size_of_image)
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<&'_ CodeId> for PeCodeId {
+    type Error = ParseCodeIdError;
+
+    fn try_from(code_id: &CodeId) -> Result<Self, ParseCodeIdError> {
+        code_id.as_str().parse()
+    }
+}
+
+/// The GNU build id of an ELF file.
 ///
-/// This identifier assumes a string representation that depends on the platform and compiler used.
-/// The representation only retains hex characters and canonically stores lower case.
+/// Contrary to the lenient parsing of [`CodeId`], `FromStr` requires a hex string of whole bytes
+/// with at least 8 and at most 64 bytes. Uppercase hex digits are converted to lowercase.
 ///
-/// There are the following known formats:
+/// # Example
 ///
-///  - **MachO UUID**: The unique identifier of a Mach binary, specified in the `LC_UUID` load
-///    command header.
-///  - **GNU Build ID**: Contents of the `.gnu.build-id` note or section contents formatted as
-///    lowercase hex string.
-///  - **PE Timestamp**: Timestamp and size of image values from a Windows PE header. The size of
-///    image value is truncated, so the length of the `CodeId` might not be a multiple of 2.
-#[derive(Clone, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct CodeId {
-    inner: String,
+/// ```
+/// use debugid::{CodeIdKind, GnuBuildId};
+///
+/// let build_id: GnuBuildId = "dfb8e43af2423d73a453aeb6a777ef75a0b1c2d3".parse().unwrap();
+/// assert_eq!(build_id.as_code_id().kind(), CodeIdKind::GnuBuildId);
+/// assert!("dfb8e43a".parse::<GnuBuildId>().is_err());
+/// ```
+///
+/// [`CodeId`]: struct.CodeId.html
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct GnuBuildId {
+    inner: CodeId,
 }
 
-impl CodeId {
-    /// Constructs an empty code identifier.
-    pub fn nil() -> Self {
-        Self::default()
+#[cfg(feature = "std")]
+impl GnuBuildId {
+    /// Returns the hex representation of this build id.
+    pub fn as_str(&self) -> &str {
+        self.inner.as_str()
     }
 
-    /// Constructs a `CodeId` from its string representation.
-    pub fn new(mut string: String) -> Self {
-        string.retain(|c| c.is_ascii_hexdigit());
-        string.make_ascii_lowercase();
-        CodeId { inner: string }
+    /// Returns this build id as `CodeId` of kind [`CodeIdKind::GnuBuildId`].
+    ///
+    /// [`CodeIdKind::GnuBuildId`]: enum.CodeIdKind.html#variant.GnuBuildId
+    pub fn as_code_id(&self) -> &CodeId {
+        &self.inner
     }
+}
 
-    /// Constructs a `CodeId` from a binary slice.
-    pub fn from_binary(slice: &[u8]) -> Self {
-        let mut string = String::with_capacity(slice.len() * 2);
+#[cfg(feature = "std")]
+impl fmt::Display for GnuBuildId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
-        for byte in slice {
-            write!(&mut string, "{:02x}", byte).expect("");
-        }
+#[cfg(feature = "std")]
+impl str::FromStr for GnuBuildId {
+    type Err = ParseCodeIdError;
 
-        Self::new(string)
-    }
+    fn from_str(string: &str) -> Result<Self, ParseCodeIdError> {
+        let code_id = CodeId::parse_hex(string)?;
+        code_id.validate_for(FileFormat::Elf)?;
 
-    /// Returns whether this identifier is nil, i.e. it is empty.
-    pub fn is_nil(&self) -> bool {
-        self.inner.is_empty()
+        Ok(GnuBuildId {
+            inner: code_id.with_kind(CodeIdKind::GnuBuildId),
+        })
     }
+}
 
-    /// Returns the string representation of this code identifier.
-    pub fn as_str(&self) -> &str {
-        self.inner.as_str()
+#[cfg(feature = "std")]
+impl From<GnuBuildId> for CodeId {
+    fn from(build_id: GnuBuildId) -> Self {
+        build_id.inner
     }
 }
 
-impl fmt::Display for CodeId {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.inner)
+#[cfg(feature = "std")]
+impl TryFrom<&'_ CodeId> for GnuBuildId {
+    type Error = ParseCodeIdError;
+
+    fn try_from(code_id: &CodeId) -> Result<Self, ParseCodeIdError> {
+        code_id.as_str().parse()
     }
 }
 
-impl fmt::Debug for CodeId {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "CodeId({})", self)
-    }
+/// How well a candidate module matches a wanted debug identifier.
+///
+/// Returned by [`match_module`]. Variants are ordered by quality, so the best of several
+/// candidates is the maximum.
+///
+/// [`match_module`]: fn.match_module.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum MatchQuality {
+    /// The candidate does not match.
+    None,
+    /// The debug identifier of the candidate only differs in the appendix.
+    ///
+    /// For PDB files, this means the candidate was written by a different link of the same
+    /// build. Symbols may be inaccurate, but are often usable as a fallback.
+    AgeMismatch,
+    /// The wanted debug identifier is derived from the code identifier of the candidate.
+    ///
+    /// See [`DebugId::matches_code_id`](struct.DebugId.html#method.matches_code_id).
+    CodeId,
+    /// The debug identifier of the candidate is equal to the wanted identifier.
+    Exact,
 }
 
-impl From<String> for CodeId {
-    fn from(string: String) -> Self {
-        Self::new(string)
+/// Determines how well a candidate module matches a wanted debug identifier.
+///
+/// This applies the rules commonly used to find debug files for the modules of a crash report,
+/// from the best to the worst match:
+///
+///  1. [`MatchQuality::Exact`] if the debug identifier of the candidate is equal.
+///  2. [`MatchQuality::CodeId`] if the wanted identifier is derived from the code identifier of
+///     the candidate, which covers ELF and MachO files whose debug identifier is unknown.
+///  3. [`MatchQuality::AgeMismatch`] if the debug identifier of the candidate has the same UUID,
+///     or the same timestamp for PDB 2.0 identifiers, but a different appendix.
+///
+/// Nil identifiers never match, since they indicate missing information rather than a specific
+/// file.
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use debugid::{match_module, CodeId, DebugId, MatchQuality};
+///
+/// let wanted = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+/// let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+/// assert_eq!(match_module(&wanted, None, Some(&code_id)), MatchQuality::CodeId);
+///
+/// let candidate = wanted.with_appendix(2);
+/// assert_eq!(match_module(&wanted, Some(&candidate), None), MatchQuality::AgeMismatch);
+/// ```
+///
+/// [`MatchQuality::Exact`]: enum.MatchQuality.html#variant.Exact
+/// [`MatchQuality::CodeId`]: enum.MatchQuality.html#variant.CodeId
+/// [`MatchQuality::AgeMismatch`]: enum.MatchQuality.html#variant.AgeMismatch
+#[cfg(feature = "std")]
+pub fn match_module(
+    wanted: &DebugId,
+    candidate_debug: Option<&DebugId>,
+    candidate_code: Option<&CodeId>,
+) -> MatchQuality {
+    if wanted.is_nil() {
+        return MatchQuality::None;
+    }
+
+    let candidate_debug = candidate_debug.filter(|debug_id| !debug_id.is_nil());
+    if candidate_debug == Some(wanted) {
+        return MatchQuality::Exact;
+    }
+
+    if candidate_code.map_or(false, |code_id| wanted.matches_code_id(code_id)) {
+        return MatchQuality::CodeId;
+    }
+
+    match candidate_debug {
+        Some(debug_id) if debug_id.zero_appendix() == wanted.zero_appendix() => {
+            MatchQuality::AgeMismatch
+        }
+        _ => MatchQuality::None,
     }
 }
 
-impl From<&'_ str> for CodeId {
-    fn from(string: &str) -> Self {
-        Self::new(string.into())
+/// Wrapper around [`CodeId`] for uppercase formatting.
+///
+/// Returned by [`CodeId::uppercase`].
+///
+/// [`CodeId`]: struct.CodeId.html
+/// [`CodeId::uppercase`]: struct.CodeId.html#method.uppercase
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct UppercaseFormat<'a> {
+    inner: &'a CodeId,
+}
+
+#[cfg(feature = "std")]
+impl<'a> fmt::Display for UppercaseFormat<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad_with(f, |w| {
+            for c in self.inner.as_str().chars() {
+                w.write_char(c.to_ascii_uppercase())?;
+            }
+            Ok(())
+        })
     }
 }
 
-impl AsRef<str> for CodeId {
-    fn as_ref(&self) -> &str {
-        self.as_str()
+/// Wrapper around [`CodeId`] for formatting padded to whole bytes.
+///
+/// Returned by [`CodeId::padded`].
+///
+/// [`CodeId`]: struct.CodeId.html
+/// [`CodeId::padded`]: struct.CodeId.html#method.padded
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct PaddedFormat<'a> {
+    inner: &'a CodeId,
+}
+
+#[cfg(feature = "std")]
+impl<'a> fmt::Display for PaddedFormat<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad_with(f, |w| {
+            let hex = self.inner.as_str();
+            if self.inner.kind().is_byte_oriented() && hex.len() % 2 == 1 {
+                w.write_char('0')?;
+            }
+            w.write_str(hex)
+        })
     }
 }
 
-impl str::FromStr for CodeId {
-    type Err = ParseCodeIdError;
+/// Wrapper around [`CodeId`] for grouped formatting.
+///
+/// Returned by [`CodeId::chunked`].
+///
+/// [`CodeId`]: struct.CodeId.html
+/// [`CodeId::chunked`]: struct.CodeId.html#method.chunked
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ChunkedFormat<'a> {
+    inner: &'a CodeId,
+    group_len: usize,
+    separator: char,
+}
 
-    fn from_str(string: &str) -> Result<Self, ParseCodeIdError> {
-        Ok(Self::new(string.into()))
+#[cfg(feature = "std")]
+impl<'a> fmt::Display for ChunkedFormat<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        pad_with(f, |w| {
+            for (index, c) in self.inner.as_str().chars().enumerate() {
+                if self.group_len > 0 && index > 0 && index % self.group_len == 0 {
+                    w.write_char(self.separator)?;
+                }
+                w.write_char(c)?;
+            }
+            Ok(())
+        })
     }
 }
 
@@ -500,16 +4410,182 @@ mod serde_support {
 
     use super::*;
 
+    // Binary formats store code identifiers as bytes, prefixed with one of the following tags.
+    // Identifiers with an odd number of hex digits are padded with a zero nibble, and strings
+    // that are not lowercase hex, such as preserved identifiers, are stored verbatim. Identifiers
+    // of a kind other than `CodeIdKind::Opaque` are prefixed with `TAG_KIND` and the kind.
+    const TAG_EVEN: u8 = 0;
+    const TAG_ODD: u8 = 1;
+    const TAG_RAW: u8 = 2;
+    const TAG_KIND: u8 = 3;
+
+    fn encode_kind(kind: CodeIdKind) -> Option<u8> {
+        Some(match kind {
+            CodeIdKind::Opaque => return None,
+            CodeIdKind::PeTimestampSize => 1,
+            CodeIdKind::GnuBuildId => 2,
+            CodeIdKind::MachUuid => 3,
+            CodeIdKind::WasmBuildId => 4,
+            CodeIdKind::GoBuildId => 5,
+            CodeIdKind::DebugLinkCrc => 6,
+            CodeIdKind::Text => 7,
+        })
+    }
+
+    fn decode_kind(kind: u8) -> CodeIdKind {
+        match kind {
+            1 => CodeIdKind::PeTimestampSize,
+            2 => CodeIdKind::GnuBuildId,
+            3 => CodeIdKind::MachUuid,
+            4 => CodeIdKind::WasmBuildId,
+            5 => CodeIdKind::GoBuildId,
+            6 => CodeIdKind::DebugLinkCrc,
+            7 => CodeIdKind::Text,
+            // Kinds added in later versions are read as opaque identifiers.
+            _ => CodeIdKind::Opaque,
+        }
+    }
+
+    fn encode_code_id(hex: &str, kind: CodeIdKind) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(hex.len() + 3);
+        if let Some(kind) = encode_kind(kind) {
+            encoded.push(TAG_KIND);
+            encoded.push(kind);
+        }
+
+        let is_lower_hex = hex.bytes().all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f'));
+        if !is_lower_hex {
+            encoded.push(TAG_RAW);
+            encoded.extend_from_slice(hex.as_bytes());
+            return encoded;
+        }
+
+        encoded.push(if hex.len() % 2 == 0 {
+            TAG_EVEN
+        } else {
+            TAG_ODD
+        });
+        encoded.extend(hex.as_bytes().chunks(2).map(|pair| {
+            let high = hex_value(pair[0]).unwrap_or_default();
+            let low = pair.get(1).and_then(|&c| hex_value(c)).unwrap_or_default();
+            high << 4 | low
+        }));
+        encoded
+    }
+
+    fn decode_code_id(encoded: &[u8]) -> Option<CodeId> {
+        let (kind, encoded) = match encoded {
+            [TAG_KIND, kind, rest @ ..] => (decode_kind(*kind), rest),
+            _ => (CodeIdKind::Opaque, encoded),
+        };
+
+        let (&tag, data) = encoded.split_first()?;
+
+        let mut inner = CodeIdBuf::default();
+        match tag {
+            TAG_EVEN => {
+                for byte in data {
+                    write!(&mut inner, "{:02x}", byte).ok()?;
+                }
+            }
+            TAG_ODD => {
+                let (&last, data) = data.split_last()?;
+                if last & 0xf != 0 {
+                    return None;
+                }
+                for byte in data {
+                    write!(&mut inner, "{:02x}", byte).ok()?;
+                }
+                write!(&mut inner, "{:x}", last >> 4).ok()?;
+            }
+            TAG_RAW => inner.write_str(str::from_utf8(data).ok()?).ok()?,
+            _ => return None,
+        }
+
+        Some(CodeId { inner, kind })
+    }
+
+    // Hex strings, optionally with hyphens as in UUIDs, are normalized like `CodeId::new`. All
+    // other strings are kept verbatim, so that text identifiers survive a round trip.
+    fn parse_code_id(value: &str) -> CodeId {
+        if value.bytes().all(|c| c.is_ascii_hexdigit() || c == b'-') {
+            CodeId::new(value.into())
+        } else {
+            CodeId::from_text(value).unwrap_or_else(|_| CodeId::new_preserving(value.into()))
+        }
+    }
+
+    fn serialize_code_id<S: Serializer>(
+        hex: &str,
+        kind: CodeIdKind,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(hex)
+        } else {
+            serializer.serialize_bytes(&encode_code_id(hex, kind))
+        }
+    }
+
+    /// Serializes as hex string in human-readable formats, and as compact bytes including the
+    /// kind otherwise.
     impl Serialize for CodeId {
         fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-            serializer.serialize_str(self.as_str())
+            serialize_code_id(self.as_str(), self.kind, serializer)
         }
     }
 
     impl<'de> Deserialize<'de> for CodeId {
         fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-            let string = String::deserialize(deserializer)?;
-            Ok(CodeId::new(string))
+            struct V;
+
+            impl<'de> Visitor<'de> for V {
+                type Value = CodeId;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("CodeId")
+                }
+
+                fn visit_str<E: de::Error>(self, value: &str) -> Result<CodeId, E> {
+                    Ok(parse_code_id(value))
+                }
+
+                fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<CodeId, E> {
+                    decode_code_id(value)
+                        .ok_or_else(|| de::Error::invalid_value(Unexpected::Bytes(value), &self))
+                }
+            }
+
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_string(V)
+            } else {
+                deserializer.deserialize_bytes(V)
+            }
+        }
+    }
+
+    impl Serialize for CodeIdArc {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize_code_id(self.as_str(), self.kind, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for CodeIdArc {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            CodeId::deserialize(deserializer).map(CodeIdArc::from)
+        }
+    }
+
+    impl Serialize for FatCodeId {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.slices.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FatCodeId {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let slices = BTreeMap::deserialize(deserializer)?;
+            Ok(FatCodeId { slices })
         }
     }
 
@@ -540,4 +4616,18 @@ mod serde_support {
             serializer.serialize_str(&self.to_string())
         }
     }
+
+    impl<'de> Deserialize<'de> for NonNilDebugId {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let debug_id = DebugId::deserialize(deserializer)?;
+            NonNilDebugId::new(debug_id)
+                .ok_or_else(|| de::Error::invalid_value(Unexpected::Str("nil"), &"NonNilDebugId"))
+        }
+    }
+
+    impl Serialize for NonNilDebugId {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.get().serialize(serializer)
+        }
+    }
 }
@@ -0,0 +1,142 @@
+//! Scanning of local symbol stores.
+//!
+//! This module is available with the `scan` feature. It walks a directory containing files in any
+//! of the layouts supported by [`SymbolKey`] and recovers the identifier of every file from its
+//! path, which is useful to prune caches or to upload the contents of a store to another server.
+//!
+//! [`SymbolKey`]: ../symkey/enum.SymbolKey.html
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::symkey::{KeyId, SymbolKey};
+
+/// A file found in a symbol store.
+///
+/// See [`scan`](fn.scan.html).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct StoreEntry {
+    /// The path of the file, including the root of the store.
+    pub path: PathBuf,
+    /// The key derived from the path, which determines the layout and kind of the file.
+    pub key: SymbolKey,
+}
+
+impl StoreEntry {
+    /// Returns the identifier of the file.
+    pub fn id(&self) -> KeyId {
+        match self.key {
+            SymbolKey::Symstore { debug_id, .. }
+            | SymbolKey::SsqpDebug { debug_id, .. }
+            | SymbolKey::Breakpad { debug_id, .. } => KeyId::Debug(debug_id),
+            SymbolKey::SymstoreCode { ref code_id, .. }
+            | SymbolKey::SsqpCode { ref code_id, .. }
+            | SymbolKey::Debuginfod { ref code_id, .. }
+            | SymbolKey::DebuginfodSection { ref code_id, .. } => KeyId::Code(code_id.clone()),
+            SymbolKey::Unified { ref id, .. } => id.clone(),
+        }
+    }
+}
+
+/// Walks a symbol store and yields all files with a valid key.
+///
+/// The key of each file is parsed from the last three components of its path relative to the
+/// root, or the last four for debuginfod section paths, see [`SymbolKey`] for the recognized
+/// layouts. This allows the store to be nested in
+/// further directories, for example per bucket or per upload. Files that do not match any layout,
+/// such as index files of `symstore.exe`, are skipped, as are paths that are not valid UTF-8.
+///
+/// Directories are visited depth-first in the order returned by the operating system. Symbolic
+/// links are not followed into directories. Errors reading a directory are yielded, after which
+/// the scan continues with the remaining directories.
+///
+/// # Example
+///
+/// ```no_run
+/// use debugid::scan::scan;
+///
+/// for entry in scan("/var/cache/symbols") {
+///     let entry = entry?;
+///     println!("{} {}", entry.path.display(), entry.key);
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [`SymbolKey`]: ../symkey/enum.SymbolKey.html
+pub fn scan<P: AsRef<Path>>(root: P) -> Scan {
+    let root = root.as_ref().to_path_buf();
+    let (stack, error) = match fs::read_dir(&root) {
+        Ok(dir) => (vec![dir], None),
+        Err(error) => (Vec::new(), Some(error)),
+    };
+
+    Scan { root, stack, error }
+}
+
+/// An iterator over the files in a symbol store.
+///
+/// See [`scan`](fn.scan.html).
+#[derive(Debug)]
+pub struct Scan {
+    root: PathBuf,
+    stack: Vec<fs::ReadDir>,
+    error: Option<io::Error>,
+}
+
+impl Scan {
+    /// Parses the key from the last components of the path relative to the root.
+    ///
+    /// Keys consist of three components, except for debuginfod section paths, which have four.
+    fn parse_key(&self, path: &Path) -> Option<SymbolKey> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let components = relative
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect::<Option<Vec<_>>>()?;
+
+        let len = components.len();
+        let count = match len.checked_sub(2).map(|index| components[index]) {
+            Some("section") if len >= 4 => 4,
+            _ => 3,
+        };
+
+        let start = len.checked_sub(count)?;
+        SymbolKey::parse(&components[start..].join("/")).ok()
+    }
+}
+
+impl Iterator for Scan {
+    type Item = io::Result<StoreEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+
+        loop {
+            let entry = match self.stack.last_mut()?.next() {
+                Some(Ok(entry)) => entry,
+                Some(Err(error)) => return Some(Err(error)),
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+
+            let path = entry.path();
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => match fs::read_dir(&path) {
+                    Ok(dir) => self.stack.push(dir),
+                    Err(error) => return Some(Err(error)),
+                },
+                Ok(_) => {
+                    if let Some(key) = self.parse_key(&path) {
+                        return Some(Ok(StoreEntry { path, key }));
+                    }
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
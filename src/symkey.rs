@@ -0,0 +1,1281 @@
+//! Lookup keys of debug and code files on symbol servers.
+//!
+//! Symbol servers store files at paths derived from the file name and an identifier of the file.
+//! This module builds these paths for the layouts used by common symbol servers, such as
+//! Microsoft's symbol server, servers implementing the Simple Symbol Query Protocol (SSQP),
+//! Breakpad symbol stores, debuginfod servers and Sentry's unified symbol server layout.
+
+use std::error;
+use std::fmt::{self, Write};
+use std::str;
+
+use crate::{
+    fmt_parse_error, map_offsets, CodeId, CodeIdKind, DebugId, ErrorInput, ParseErrorKind,
+    ParseOptions,
+};
+
+/// Indicates an error parsing a symbol server key.
+///
+/// Like other parse errors of this crate, this records the [`ParseErrorKind`] and the byte
+/// offset in the key at which parsing failed. Errors in identifiers are reported with the offset
+/// of the offending character in the key.
+///
+/// [`ParseErrorKind`]: ../enum.ParseErrorKind.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseKeyError {
+    kind: ParseErrorKind,
+    offset: usize,
+    input: Option<ErrorInput>,
+}
+
+impl ParseKeyError {
+    fn new(kind: ParseErrorKind, offset: usize) -> Self {
+        ParseKeyError {
+            kind,
+            offset,
+            input: None,
+        }
+    }
+
+    fn with_input(mut self, input: &str) -> Self {
+        if !input.is_empty() {
+            self.input = Some(ErrorInput::new(input));
+        }
+        self
+    }
+
+    /// Returns the reason why parsing failed.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset in the key at which parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the beginning of the rejected key.
+    ///
+    /// Long keys are truncated, see [`is_input_truncated`](#method.is_input_truncated).
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_ref().map(|input| input.buf.as_str())
+    }
+
+    /// Returns whether [`input`](#method.input) was truncated.
+    pub fn is_input_truncated(&self) -> bool {
+        self.input.map_or(false, |input| input.truncated)
+    }
+}
+
+impl error::Error for ParseKeyError {}
+
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_parse_error(f, "symbol key", self.kind, self.offset, self.input.as_ref())
+    }
+}
+
+/// The kind of file referenced by an SSQP key.
+///
+/// See [`CodeId::ssqp_key`](../struct.CodeId.html#method.ssqp_key).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SsqpKind {
+    /// An ELF executable or library, keyed by its GNU build id.
+    ElfBinary,
+    /// The debug companion file of an ELF file, keyed by its GNU build id.
+    ElfSymbols,
+    /// A Mach-O executable or library, keyed by its `LC_UUID`.
+    MachBinary,
+    /// The dSYM companion file of a Mach-O file, keyed by its `LC_UUID`.
+    MachSymbols,
+    /// A PE executable or library, keyed by its timestamp and size of image.
+    PeBinary,
+}
+
+/// The kind of file requested from a debuginfod server.
+///
+/// See [`CodeId::debuginfod_path`](../struct.CodeId.html#method.debuginfod_path). Individual
+/// sections are requested with
+/// [`CodeId::debuginfod_section_path`](../struct.CodeId.html#method.debuginfod_section_path).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DebuginfodKind {
+    /// The debug companion file containing DWARF debug information.
+    Debuginfo,
+    /// The executable or library itself.
+    Executable,
+}
+
+/// The kind of file stored in Sentry's unified symbol server layout.
+///
+/// See [`DebugId::unified_path`](../struct.DebugId.html#method.unified_path) and
+/// [`CodeId::unified_path`](../struct.CodeId.html#method.unified_path).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum UnifiedFileType {
+    /// A file containing debug information, such as a PDB, dSYM or ELF debug companion.
+    Debuginfo,
+    /// The executable or library itself.
+    Executable,
+    /// A source bundle with the sources referenced by a debug file.
+    SourceBundle,
+}
+
+/// The identifier recovered from a symbol server key.
+///
+/// See [`parse_key`](fn.parse_key.html).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum KeyId {
+    /// The debug identifier of a PDB or Breakpad symbol file.
+    Debug(DebugId),
+    /// The code identifier of an executable, library or debug companion file.
+    Code(CodeId),
+}
+
+/// Controls the case of a component of a symbol server key.
+///
+/// See [`KeyOptions`](struct.KeyOptions.html).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CasePolicy {
+    /// Uses the case of the layout.
+    ///
+    /// This is the case produced by the key builders: symstore and Breakpad keys contain the
+    /// file name as given and an uppercase identifier, while SSQP, debuginfod and unified keys
+    /// are lowercase.
+    Canonical,
+    /// Converts the component to lowercase.
+    Lowercase,
+    /// Converts the component to uppercase.
+    Uppercase,
+}
+
+impl CasePolicy {
+    fn apply(self, string: String) -> String {
+        match self {
+            CasePolicy::Canonical => string,
+            CasePolicy::Lowercase => string.to_lowercase(),
+            CasePolicy::Uppercase => string.to_uppercase(),
+        }
+    }
+}
+
+impl Default for CasePolicy {
+    fn default() -> Self {
+        CasePolicy::Canonical
+    }
+}
+
+/// Options to control how [`SymbolKey`]s are formatted.
+///
+/// Symbol servers differ in how they treat the case of keys, so the case of file names and
+/// identifiers can be configured separately with a [`CasePolicy`]. Fixed parts of a key, such as
+/// the `elf-buildid-` prefix of SSQP keys or the file type of debuginfod paths, are never
+/// changed. The options returned by [`KeyOptions::new`] match `fmt::Display` for `SymbolKey`.
+///
+/// File names are inserted verbatim by default. To use keys directly in URLs or object store
+/// keys, enable [`KeyOptions::percent_encode`] or validate names with [`check_file_name`].
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use debugid::DebugId;
+/// use debugid::symkey::{CasePolicy, KeyOptions, SymbolKey};
+///
+/// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+/// let key = SymbolKey::symstore("WKernel32.pdb", id);
+/// let options = KeyOptions::new().file_name_case(CasePolicy::Lowercase);
+/// assert_eq!(
+///     key.format(options),
+///     "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pdb"
+/// );
+/// ```
+///
+/// [`SymbolKey`]: enum.SymbolKey.html
+/// [`CasePolicy`]: enum.CasePolicy.html
+/// [`KeyOptions::new`]: struct.KeyOptions.html#method.new
+/// [`KeyOptions::percent_encode`]: struct.KeyOptions.html#method.percent_encode
+/// [`check_file_name`]: fn.check_file_name.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KeyOptions {
+    file_name_case: CasePolicy,
+    id_case: CasePolicy,
+    percent_encode: bool,
+}
+
+impl KeyOptions {
+    /// Returns the options used by `fmt::Display`, which use the canonical case of each layout.
+    pub fn new() -> Self {
+        KeyOptions {
+            file_name_case: CasePolicy::Canonical,
+            id_case: CasePolicy::Canonical,
+            percent_encode: false,
+        }
+    }
+
+    /// Returns the options for keys on Mozilla's Tecken symbol server.
+    ///
+    /// Tecken expects lowercase file names and an entirely uppercase identifier. This differs
+    /// from the canonical Breakpad layout, which retains the case of the file name and formats
+    /// the appendix in lowercase. See [`tecken_sym_path`](fn.tecken_sym_path.html).
+    pub fn tecken() -> Self {
+        Self::new()
+            .file_name_case(CasePolicy::Lowercase)
+            .id_case(CasePolicy::Uppercase)
+    }
+
+    /// Sets the case of file names and identifiers.
+    pub fn case(self, policy: CasePolicy) -> Self {
+        self.file_name_case(policy).id_case(policy)
+    }
+
+    /// Sets the case of file names.
+    pub fn file_name_case(mut self, policy: CasePolicy) -> Self {
+        self.file_name_case = policy;
+        self
+    }
+
+    /// Sets the case of debug and code identifiers.
+    pub fn id_case(mut self, policy: CasePolicy) -> Self {
+        self.id_case = policy;
+        self
+    }
+
+    /// Sets whether file names are percent-encoded.
+    ///
+    /// If enabled, all bytes of file names except ASCII letters, digits and `-._~+` are encoded
+    /// as `%XX`. This includes path separators, whitespace and the UTF-8 encoding of non-ASCII
+    /// characters. The special names `.` and `..` are encoded entirely as `%2E`. The encoding is
+    /// applied after the [`CasePolicy`](enum.CasePolicy.html).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use debugid::DebugId;
+    /// use debugid::symkey::{KeyOptions, SymbolKey};
+    ///
+    /// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    /// let key = SymbolKey::symstore("my app.pdb", id);
+    /// assert_eq!(
+    ///     key.format(KeyOptions::new().percent_encode(true)),
+    ///     "my%20app.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/my%20app.pdb"
+    /// );
+    /// ```
+    pub fn percent_encode(mut self, encode: bool) -> Self {
+        self.percent_encode = encode;
+        self
+    }
+}
+
+impl Default for KeyOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the name of a file compressed with `makecab`, as stored by `symstore.exe /compress`.
+///
+/// The last character of the file name is replaced with an underscore, for example `xul.pdb`
+/// becomes `xul.pd_`.
+///
+/// # Example
+///
+/// ```
+/// use debugid::symkey::compressed_file_name;
+///
+/// assert_eq!(compressed_file_name("ntdll.dll"), "ntdll.dl_");
+/// ```
+pub fn compressed_file_name(file_name: &str) -> String {
+    let mut chars = file_name.chars();
+    chars.next_back();
+    format!("{}_", chars.as_str())
+}
+
+/// Returns the path of a Breakpad symbol file in a symbol store.
+///
+/// The path has the form `<file name>/<id>/<sym name>`, where the identifier is the debug id in
+/// the breakpad format, see [`DebugId::breakpad`]. The name of the symbol file is derived from
+/// the debug file: a `.pdb` extension is replaced with `.sym`, otherwise `.sym` is appended. The
+/// debug file may be a full path, in which case only the file name is used.
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use debugid::DebugId;
+/// use debugid::symkey::breakpad_sym_path;
+///
+/// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+/// assert_eq!(
+///     breakpad_sym_path("wkernel32.pdb", &id),
+///     "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.sym"
+/// );
+///
+/// let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+/// assert_eq!(
+///     breakpad_sym_path("/usr/lib/libfoo.so", &id),
+///     "libfoo.so/DFB8E43AF2423D73A453AEB6A777EF750/libfoo.so.sym"
+/// );
+/// ```
+///
+/// [`DebugId::breakpad`]: ../struct.DebugId.html#method.breakpad
+pub fn breakpad_sym_path(debug_file: &str, debug_id: &DebugId) -> String {
+    sym_path(file_name(debug_file), &debug_id.breakpad().to_string())
+}
+
+/// Returns the path of a Breakpad symbol file on Mozilla's Tecken symbol server.
+///
+/// This is the same as [`breakpad_sym_path`], except that the file name is converted to lowercase
+/// and the identifier is entirely uppercase, including the appendix. The canonical breakpad
+/// format uses a lowercase appendix, so the paths differ for debug files with an age of ten or
+/// more.
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use debugid::DebugId;
+/// use debugid::symkey::tecken_sym_path;
+///
+/// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-a").unwrap();
+/// assert_eq!(
+///     tecken_sym_path("WKernel32.pdb", &id),
+///     "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B5A/wkernel32.sym"
+/// );
+/// ```
+///
+/// [`breakpad_sym_path`]: fn.breakpad_sym_path.html
+pub fn tecken_sym_path(debug_file: &str, debug_id: &DebugId) -> String {
+    SymbolKey::breakpad(debug_file, *debug_id).format(KeyOptions::tecken())
+}
+
+/// Checks whether a file name can be used in symbol server keys without encoding.
+///
+/// File names must only consist of ASCII letters, digits and `-._~+`, so that keys built from
+/// them can be used directly in URLs and object store keys. The special names `.` and `..` are
+/// rejected since they alter the meaning of paths. Use this to reject file names from untrusted
+/// input, or use [`KeyOptions::percent_encode`] to encode them instead.
+///
+/// The error reports [`ParseErrorKind::NonAscii`] or [`ParseErrorKind::InvalidFormat`] at the
+/// offset of the first rejected character.
+///
+/// # Example
+///
+/// ```
+/// use debugid::symkey::check_file_name;
+///
+/// assert!(check_file_name("libstdc++.so.6").is_ok());
+/// assert_eq!(check_file_name("../xul.pdb").unwrap_err().offset(), 2);
+/// ```
+///
+/// [`KeyOptions::percent_encode`]: struct.KeyOptions.html#method.percent_encode
+/// [`ParseErrorKind::NonAscii`]: ../enum.ParseErrorKind.html#variant.NonAscii
+/// [`ParseErrorKind::InvalidFormat`]: ../enum.ParseErrorKind.html#variant.InvalidFormat
+pub fn check_file_name(name: &str) -> Result<(), ParseKeyError> {
+    let error = |kind, offset| Err(ParseKeyError::new(kind, offset).with_input(name));
+
+    match name {
+        "" => return error(ParseErrorKind::Empty, 0),
+        "." | ".." => return error(ParseErrorKind::InvalidFormat, 0),
+        _ => (),
+    }
+
+    match name.bytes().position(|c| !is_url_safe(c)) {
+        Some(offset) if !name.as_bytes()[offset].is_ascii() => {
+            error(ParseErrorKind::NonAscii, offset)
+        }
+        Some(offset) => error(ParseErrorKind::InvalidFormat, offset),
+        None => Ok(()),
+    }
+}
+
+/// Parses a symbol server key into the file name and the identifier of the file.
+///
+/// This is the inverse of the key builders in this module and accepts keys of the form
+/// `<file name>/<id>/<file name>`:
+///
+///  - Keys of PDB files, compressed PDB files and Breakpad symbol files, whose last component
+///    ends with `.pdb`, `.pd_` or `.sym`, contain a [`DebugId`] in the breakpad format.
+///  - SSQP keys of ELF and Mach-O files contain a [`CodeId`] with an `elf-buildid-` or
+///    `mach-uuid-` prefix, optionally followed by `sym-`.
+///  - All other keys contain the [`CodeId`] of a PE file, see [`CodeId::parse_pe`].
+///
+/// The returned file name is the first component of the key. SSQP keys of symbol files use
+/// placeholder names such as `_.debug`, which are returned as they are.
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use debugid::DebugId;
+/// use debugid::symkey::{parse_key, KeyId};
+///
+/// let key = "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pdb";
+/// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+/// assert_eq!(parse_key(key), Ok(("wkernel32.pdb", KeyId::Debug(id))));
+/// ```
+///
+/// [`DebugId`]: ../struct.DebugId.html
+/// [`CodeId`]: ../struct.CodeId.html
+/// [`CodeId::parse_pe`]: ../struct.CodeId.html#method.parse_pe
+pub fn parse_key(key: &str) -> Result<(&str, KeyId), ParseKeyError> {
+    match parse_key_components(key) {
+        Ok((name, id, _)) => Ok((name, id)),
+        Err(error) => Err(error.with_input(key)),
+    }
+}
+
+impl DebugId {
+    /// Returns the key of a PDB file on a Microsoft symbol server.
+    ///
+    /// The key has the form `<file name>/<id>/<file name>`, where the identifier consists of the
+    /// GUID and the age in uppercase hex without separators, as written by `symstore.exe` and
+    /// expected by `symsrv.dll`. The debug file may be a full path, in which case only the file
+    /// name is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use debugid::DebugId;
+    ///
+    /// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    /// assert_eq!(
+    ///     id.symstore_key("C:\\build\\wkernel32.pdb"),
+    ///     "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pdb"
+    /// );
+    /// ```
+    pub fn symstore_key(&self, debug_file: &str) -> String {
+        let name = file_name(debug_file);
+        format!("{}/{:X}/{}", name, self, name)
+    }
+
+    /// Returns the key of a compressed PDB file on a Microsoft symbol server.
+    ///
+    /// This is the same as [`DebugId::symstore_key`], except that the last component uses the
+    /// name of the compressed file, see [`compressed_file_name`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use debugid::DebugId;
+    ///
+    /// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    /// assert_eq!(
+    ///     id.symstore_compressed_key("wkernel32.pdb"),
+    ///     "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pd_"
+    /// );
+    /// ```
+    ///
+    /// [`DebugId::symstore_key`]: ../struct.DebugId.html#method.symstore_key
+    /// [`compressed_file_name`]: fn.compressed_file_name.html
+    pub fn symstore_compressed_key(&self, debug_file: &str) -> String {
+        let name = file_name(debug_file);
+        format!("{}/{:X}/{}", name, self, compressed_file_name(name))
+    }
+
+    /// Returns the keys of a PDB file on a Microsoft symbol server, uncompressed first.
+    ///
+    /// Symbol servers may store each file either uncompressed or compressed, so clients probe
+    /// both keys. See [`DebugId::symstore_key`] and [`DebugId::symstore_compressed_key`].
+    ///
+    /// [`DebugId::symstore_key`]: ../struct.DebugId.html#method.symstore_key
+    /// [`DebugId::symstore_compressed_key`]: ../struct.DebugId.html#method.symstore_compressed_key
+    pub fn symstore_keys(&self, debug_file: &str) -> [String; 2] {
+        [
+            self.symstore_key(debug_file),
+            self.symstore_compressed_key(debug_file),
+        ]
+    }
+
+    /// Returns the SSQP key of a PDB file.
+    ///
+    /// The key has the form `<file name>/<id>/<file name>`, where the identifier consists of the
+    /// GUID and the age in lowercase hex without separators. SSQP keys are lowercase, so the file
+    /// name is converted to lowercase as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use debugid::DebugId;
+    ///
+    /// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    /// assert_eq!(
+    ///     id.ssqp_key("WKernel32.pdb"),
+    ///     "wkernel32.pdb/ff9f9f7841db88f0cdeda9e1e9bff3b51/wkernel32.pdb"
+    /// );
+    /// ```
+    pub fn ssqp_key(&self, debug_file: &str) -> String {
+        let name = file_name(debug_file).to_lowercase();
+        format!("{}/{:x}/{}", name, self, name)
+    }
+
+    /// Returns the path of a file in Sentry's unified symbol server layout.
+    ///
+    /// The path has the form `<prefix>/<rest>/<file type>`, where the prefix consists of the first
+    /// two characters of the simple representation and the rest of the remaining characters, see
+    /// [`DebugId::simple`]. The unified layout keys PDB files by their debug id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use debugid::DebugId;
+    /// use debugid::symkey::UnifiedFileType;
+    ///
+    /// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    /// assert_eq!(
+    ///     id.unified_path(UnifiedFileType::Debuginfo),
+    ///     "ff/9f9f7841db88f0cdeda9e1e9bff3b51/debuginfo"
+    /// );
+    /// ```
+    ///
+    /// [`DebugId::simple`]: ../struct.DebugId.html#method.simple
+    pub fn unified_path(&self, file_type: UnifiedFileType) -> String {
+        unified_path(&self.simple().to_string(), file_type)
+    }
+}
+
+impl CodeId {
+    /// Returns the SSQP key of an executable, library or debug companion file.
+    ///
+    /// The key depends on the kind of file:
+    ///
+    ///  - [`SsqpKind::ElfBinary`] uses `<file name>/elf-buildid-<id>/<file name>`.
+    ///  - [`SsqpKind::ElfSymbols`] uses `_.debug/elf-buildid-sym-<id>/_.debug`.
+    ///  - [`SsqpKind::MachBinary`] uses `<file name>/mach-uuid-<id>/<file name>`.
+    ///  - [`SsqpKind::MachSymbols`] uses `_.dwarf/mach-uuid-sym-<id>/_.dwarf`.
+    ///  - [`SsqpKind::PeBinary`] uses `<file name>/<id>/<file name>`.
+    ///
+    /// SSQP keys are lowercase, so the file name is converted to lowercase. The file name is not
+    /// used for symbol files, whose keys use fixed placeholder names.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    /// use debugid::symkey::SsqpKind;
+    ///
+    /// let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    /// assert_eq!(
+    ///     code_id.ssqp_key(SsqpKind::ElfSymbols, "libfoo.so"),
+    ///     "_.debug/elf-buildid-sym-3ae4b8df42f2733da453aeb6a777ef7593a66d4f/_.debug"
+    /// );
+    ///
+    /// let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    /// assert_eq!(
+    ///     code_id.ssqp_key(SsqpKind::PeBinary, "NTDLL.DLL"),
+    ///     "ntdll.dll/5ccc38584b08000/ntdll.dll"
+    /// );
+    /// ```
+    ///
+    /// [`SsqpKind::ElfBinary`]: symkey/enum.SsqpKind.html#variant.ElfBinary
+    /// [`SsqpKind::ElfSymbols`]: symkey/enum.SsqpKind.html#variant.ElfSymbols
+    /// [`SsqpKind::MachBinary`]: symkey/enum.SsqpKind.html#variant.MachBinary
+    /// [`SsqpKind::MachSymbols`]: symkey/enum.SsqpKind.html#variant.MachSymbols
+    /// [`SsqpKind::PeBinary`]: symkey/enum.SsqpKind.html#variant.PeBinary
+    pub fn ssqp_key(&self, kind: SsqpKind, code_file: &str) -> String {
+        let id = self.as_str().to_ascii_lowercase();
+        let name = file_name(code_file).to_lowercase();
+        ssqp_code_key(kind, &name, &id)
+    }
+
+    /// Returns the key of a PE executable or library on a Microsoft symbol server.
+    ///
+    /// The key has the form `<file name>/<id>/<file name>`, where the identifier consists of the
+    /// `TimeDateStamp` as eight uppercase hex digits followed by the `SizeOfImage` in lowercase
+    /// hex, as written by `symstore.exe`. The code file may be a full path, in which case only
+    /// the file name is used.
+    ///
+    /// Returns `None` if this is not the code identifier of a PE file, see
+    /// [`CodeId::parse_pe`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    /// assert_eq!(
+    ///     code_id.pe_file_key("C:\\Windows\\System32\\ntdll.dll").as_deref(),
+    ///     Some("ntdll.dll/5CCC38584b08000/ntdll.dll")
+    /// );
+    /// ```
+    ///
+    /// [`CodeId::parse_pe`]: ../struct.CodeId.html#method.parse_pe
+    pub fn pe_file_key(&self, code_file: &str) -> Option<String> {
+        let name = file_name(code_file);
+        let id = pe_file_id(self)?;
+        Some(format!("{}/{}/{}", name, id, name))
+    }
+
+    /// Returns the path of a file on a debuginfod server.
+    ///
+    /// The path has the form `buildid/<id>/debuginfo` or `buildid/<id>/executable`, where the
+    /// identifier is the full GNU build id in lowercase hex. The path is relative to the base URL
+    /// of the server.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    /// use debugid::symkey::DebuginfodKind;
+    ///
+    /// let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    /// assert_eq!(
+    ///     code_id.debuginfod_path(DebuginfodKind::Debuginfo),
+    ///     "buildid/3ae4b8df42f2733da453aeb6a777ef7593a66d4f/debuginfo"
+    /// );
+    /// ```
+    pub fn debuginfod_path(&self, kind: DebuginfodKind) -> String {
+        debuginfod_path(&self.as_str().to_ascii_lowercase(), kind)
+    }
+
+    /// Returns the path of an ELF section on a debuginfod server.
+    ///
+    /// The path has the form `buildid/<id>/section/<name>`. As required by the debuginfod
+    /// protocol, the section name is percent-escaped: all bytes except ASCII letters, digits and
+    /// `-._~` are encoded as `%XX`. Section names usually start with a dot, for example
+    /// `.debug_line` or `.gdb_index`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    ///
+    /// let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    /// assert_eq!(
+    ///     code_id.debuginfod_section_path(".gdb_index"),
+    ///     "buildid/3ae4b8df42f2733da453aeb6a777ef7593a66d4f/section/.gdb_index"
+    /// );
+    /// ```
+    pub fn debuginfod_section_path(&self, section: &str) -> String {
+        debuginfod_section_path(&self.as_str().to_ascii_lowercase(), section)
+    }
+
+    /// Returns the path of a file in Sentry's unified symbol server layout.
+    ///
+    /// The path has the form `<prefix>/<rest>/<file type>`, where the prefix consists of the first
+    /// two characters of the lowercase code identifier and the rest of the remaining characters.
+    /// The unified layout keys ELF and Mach-O files by their code id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use debugid::CodeId;
+    /// use debugid::symkey::UnifiedFileType;
+    ///
+    /// let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    /// assert_eq!(
+    ///     code_id.unified_path(UnifiedFileType::Executable),
+    ///     "3a/e4b8df42f2733da453aeb6a777ef7593a66d4f/executable"
+    /// );
+    /// ```
+    pub fn unified_path(&self, file_type: UnifiedFileType) -> String {
+        unified_path(&self.as_str().to_ascii_lowercase(), file_type)
+    }
+}
+
+/// The location of a debug or code file on a symbol server.
+///
+/// This combines the layouts supported by this module into a single type. Each variant contains
+/// the file name and identifier needed to build the key, and formatting a `SymbolKey` via
+/// `fmt::Display` produces the same key as the corresponding builder function.
+///
+/// Parsing via `FromStr` recognizes the layout from the structure of the key:
+///
+///  - Keys starting with `buildid/` are debuginfod paths.
+///  - Keys whose first component consists of two hex digits and whose last component is a
+///    [`UnifiedFileType`] are paths in the unified layout. The unified layout does not record
+///    whether the identifier is a debug or code identifier, so these always contain a
+///    [`KeyId::Code`].
+///  - Keys of Breakpad symbol files end with `.sym`.
+///  - Other keys of PDB files are symstore keys if the identifier is uppercase or contains no
+///    letters, and SSQP keys otherwise. Keys of compressed files parse into the key of the uncompressed file.
+///  - Keys of PE files are symstore keys if the timestamp is uppercase or contains no letters,
+///    and SSQP keys otherwise.
+///  - All other keys are SSQP keys of code files, see [`parse_key`].
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use debugid::DebugId;
+/// use debugid::symkey::SymbolKey;
+///
+/// let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+/// let key = SymbolKey::breakpad("C:\\build\\wkernel32.pdb", id);
+/// assert_eq!(
+///     key.to_string(),
+///     "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.sym"
+/// );
+/// assert_eq!(SymbolKey::from_str(&key.to_string()), Ok(key));
+/// ```
+///
+/// [`UnifiedFileType`]: enum.UnifiedFileType.html
+/// [`KeyId::Code`]: enum.KeyId.html#variant.Code
+/// [`parse_key`]: fn.parse_key.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SymbolKey {
+    /// A PDB file on a Microsoft symbol server, see
+    /// [`DebugId::symstore_key`](../struct.DebugId.html#method.symstore_key).
+    Symstore {
+        /// The file name of the debug file.
+        name: String,
+        /// The debug identifier of the debug file.
+        debug_id: DebugId,
+    },
+    /// A PDB file on an SSQP server, see
+    /// [`DebugId::ssqp_key`](../struct.DebugId.html#method.ssqp_key).
+    SsqpDebug {
+        /// The file name of the debug file.
+        name: String,
+        /// The debug identifier of the debug file.
+        debug_id: DebugId,
+    },
+    /// A PE executable or library on a Microsoft symbol server, see
+    /// [`CodeId::pe_file_key`](../struct.CodeId.html#method.pe_file_key).
+    SymstoreCode {
+        /// The file name of the code file.
+        name: String,
+        /// The code identifier of the code file.
+        code_id: CodeId,
+    },
+    /// A code file or debug companion file on an SSQP server, see
+    /// [`CodeId::ssqp_key`](../struct.CodeId.html#method.ssqp_key).
+    SsqpCode {
+        /// The kind of file.
+        kind: SsqpKind,
+        /// The file name of the code file.
+        name: String,
+        /// The code identifier of the code file.
+        code_id: CodeId,
+    },
+    /// A Breakpad symbol file in a symbol store, see
+    /// [`breakpad_sym_path`](fn.breakpad_sym_path.html).
+    Breakpad {
+        /// The file name of the debug file.
+        name: String,
+        /// The debug identifier of the debug file.
+        debug_id: DebugId,
+    },
+    /// A file on a debuginfod server, see
+    /// [`CodeId::debuginfod_path`](../struct.CodeId.html#method.debuginfod_path).
+    Debuginfod {
+        /// The kind of file.
+        kind: DebuginfodKind,
+        /// The GNU build id of the file.
+        code_id: CodeId,
+    },
+    /// An ELF section on a debuginfod server, see
+    /// [`CodeId::debuginfod_section_path`](../struct.CodeId.html#method.debuginfod_section_path).
+    DebuginfodSection {
+        /// The GNU build id of the file containing the section.
+        code_id: CodeId,
+        /// The name of the section, without percent-escaping.
+        section: String,
+    },
+    /// A file in Sentry's unified symbol server layout, see
+    /// [`DebugId::unified_path`](../struct.DebugId.html#method.unified_path).
+    Unified {
+        /// The kind of file.
+        file_type: UnifiedFileType,
+        /// The identifier of the file.
+        id: KeyId,
+    },
+}
+
+impl SymbolKey {
+    /// Creates the key of a PDB file on a Microsoft symbol server.
+    pub fn symstore(debug_file: &str, debug_id: DebugId) -> Self {
+        SymbolKey::Symstore {
+            name: file_name(debug_file).into(),
+            debug_id,
+        }
+    }
+
+    /// Creates the key of a PDB file on an SSQP server.
+    pub fn ssqp_debug(debug_file: &str, debug_id: DebugId) -> Self {
+        SymbolKey::SsqpDebug {
+            name: file_name(debug_file).into(),
+            debug_id,
+        }
+    }
+
+    /// Creates the key of a PE executable or library on a Microsoft symbol server.
+    pub fn symstore_code(code_file: &str, code_id: CodeId) -> Self {
+        SymbolKey::SymstoreCode {
+            name: file_name(code_file).into(),
+            code_id,
+        }
+    }
+
+    /// Creates the key of a code file or debug companion file on an SSQP server.
+    pub fn ssqp_code(kind: SsqpKind, code_file: &str, code_id: CodeId) -> Self {
+        SymbolKey::SsqpCode {
+            kind,
+            name: file_name(code_file).into(),
+            code_id,
+        }
+    }
+
+    /// Creates the path of a Breakpad symbol file in a symbol store.
+    pub fn breakpad(debug_file: &str, debug_id: DebugId) -> Self {
+        SymbolKey::Breakpad {
+            name: file_name(debug_file).into(),
+            debug_id,
+        }
+    }
+
+    /// Creates the path of a file on a debuginfod server.
+    pub fn debuginfod(kind: DebuginfodKind, code_id: CodeId) -> Self {
+        SymbolKey::Debuginfod { kind, code_id }
+    }
+
+    /// Creates the path of an ELF section on a debuginfod server.
+    pub fn debuginfod_section(code_id: CodeId, section: &str) -> Self {
+        SymbolKey::DebuginfodSection {
+            code_id,
+            section: section.into(),
+        }
+    }
+
+    /// Creates the path of a file in Sentry's unified symbol server layout.
+    pub fn unified(file_type: UnifiedFileType, id: KeyId) -> Self {
+        SymbolKey::Unified { file_type, id }
+    }
+
+    /// Parses a symbol key, see [`SymbolKey`](enum.SymbolKey.html) for the recognized layouts.
+    pub fn parse(key: &str) -> Result<Self, ParseKeyError> {
+        Self::parse_layout(key).map_err(|error| error.with_input(key))
+    }
+
+    fn parse_layout(key: &str) -> Result<Self, ParseKeyError> {
+        if let Some(key) = Self::parse_debuginfod(key)? {
+            return Ok(key);
+        }
+
+        if let Some(key) = Self::parse_unified(key)? {
+            return Ok(key);
+        }
+
+        let (name, id, ssqp_kind) = parse_key_components(key)?;
+        let name = name.into();
+
+        Ok(match (id, ssqp_kind) {
+            (KeyId::Code(code_id), Some(SsqpKind::PeBinary)) => {
+                let id = key.split('/').nth(1).unwrap_or_default();
+                // The timestamp consists of the first eight hex digits.
+                if is_symstore_case(&id[..8]) {
+                    SymbolKey::SymstoreCode { name, code_id }
+                } else {
+                    SymbolKey::SsqpCode {
+                        kind: SsqpKind::PeBinary,
+                        name,
+                        code_id,
+                    }
+                }
+            }
+            (KeyId::Code(code_id), kind) => SymbolKey::SsqpCode {
+                kind: kind.unwrap_or(SsqpKind::PeBinary),
+                name,
+                code_id,
+            },
+            (KeyId::Debug(debug_id), _) if strip_extension(key, ".sym").is_some() => {
+                SymbolKey::Breakpad { name, debug_id }
+            }
+            (KeyId::Debug(debug_id), _) => {
+                let id = key.split('/').nth(1).unwrap_or_default();
+                if is_symstore_case(id) {
+                    SymbolKey::Symstore { name, debug_id }
+                } else {
+                    SymbolKey::SsqpDebug { name, debug_id }
+                }
+            }
+        })
+    }
+
+    fn parse_debuginfod(key: &str) -> Result<Option<Self>, ParseKeyError> {
+        let rest = match key.strip_prefix("buildid/") {
+            Some(rest) => rest,
+            None => return Ok(None),
+        };
+
+        let (hex, kind) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index + 1..]),
+            None => return Err(ParseKeyError::new(ParseErrorKind::InvalidFormat, key.len())),
+        };
+
+        let hex_offset = "buildid/".len();
+        let code_id = match CodeId::parse_hex(hex) {
+            Ok(code_id) => code_id.with_kind(CodeIdKind::GnuBuildId),
+            Err(e) => return Err(ParseKeyError::new(e.kind(), hex_offset + e.offset())),
+        };
+
+        let kind_offset = key.len() - kind.len();
+        let kind = match kind {
+            "debuginfo" => DebuginfodKind::Debuginfo,
+            "executable" => DebuginfodKind::Executable,
+            _ => match kind.strip_prefix("section/") {
+                Some(section) if !section.is_empty() => {
+                    let section_offset = kind_offset + "section/".len();
+                    let section = percent_decode(section, section_offset)?;
+                    return Ok(Some(SymbolKey::DebuginfodSection { code_id, section }));
+                }
+                _ => {
+                    return Err(ParseKeyError::new(
+                        ParseErrorKind::InvalidFormat,
+                        kind_offset,
+                    ))
+                }
+            },
+        };
+
+        Ok(Some(SymbolKey::Debuginfod { kind, code_id }))
+    }
+
+    fn parse_unified(key: &str) -> Result<Option<Self>, ParseKeyError> {
+        let mut components = key.split('/');
+        let (prefix, rest, file_type) = match (
+            components.next(),
+            components.next(),
+            components.next(),
+            components.next(),
+        ) {
+            (Some(prefix), Some(rest), Some(file_type), None) => (prefix, rest, file_type),
+            _ => return Ok(None),
+        };
+
+        let file_type = match file_type {
+            "debuginfo" => UnifiedFileType::Debuginfo,
+            "executable" => UnifiedFileType::Executable,
+            "sourcebundle" => UnifiedFileType::SourceBundle,
+            _ => return Ok(None),
+        };
+
+        if prefix.len() != 2 || !prefix.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(None);
+        } else if rest.is_empty() {
+            return Err(ParseKeyError::new(ParseErrorKind::InvalidFormat, 3));
+        }
+
+        match CodeId::parse_hex(&format!("{}{}", prefix, rest)) {
+            Ok(code_id) => Ok(Some(SymbolKey::Unified {
+                file_type,
+                id: KeyId::Code(code_id),
+            })),
+            // Skip the separator between the prefix and the rest.
+            Err(e) => Err(ParseKeyError::new(e.kind(), e.offset() + 1)),
+        }
+    }
+
+    /// Formats the key with the given options.
+    ///
+    /// This is equivalent to `fmt::Display` if called with [`KeyOptions::new`].
+    ///
+    /// [`KeyOptions::new`]: struct.KeyOptions.html#method.new
+    pub fn format(&self, options: KeyOptions) -> String {
+        let name = |name: String| {
+            let name = options.file_name_case.apply(name);
+            if options.percent_encode {
+                percent_encode(&name, is_url_safe)
+            } else {
+                name
+            }
+        };
+        let id = |id: String| options.id_case.apply(id);
+
+        match *self {
+            SymbolKey::Symstore {
+                name: ref n,
+                ref debug_id,
+            } => {
+                let n = name(n.clone());
+                format!("{}/{}/{}", n, id(format!("{:X}", debug_id)), n)
+            }
+            SymbolKey::SsqpDebug {
+                name: ref n,
+                ref debug_id,
+            } => {
+                let n = name(n.to_lowercase());
+                format!("{}/{}/{}", n, id(format!("{:x}", debug_id)), n)
+            }
+            SymbolKey::SymstoreCode {
+                name: ref n,
+                ref code_id,
+            } => {
+                let n = name(n.clone());
+                let code = pe_file_id(code_id).unwrap_or_else(|| code_id.as_str().into());
+                format!("{}/{}/{}", n, id(code), n)
+            }
+            SymbolKey::SsqpCode {
+                kind,
+                name: ref n,
+                ref code_id,
+            } => ssqp_code_key(
+                kind,
+                &name(n.to_lowercase()),
+                &id(code_id.as_str().to_ascii_lowercase()),
+            ),
+            SymbolKey::Breakpad {
+                name: ref n,
+                ref debug_id,
+            } => sym_path(&name(n.clone()), &id(debug_id.breakpad().to_string())),
+            SymbolKey::Debuginfod { kind, ref code_id } => {
+                debuginfod_path(&id(code_id.as_str().to_ascii_lowercase()), kind)
+            }
+            SymbolKey::DebuginfodSection {
+                ref code_id,
+                ref section,
+            } => debuginfod_section_path(&id(code_id.as_str().to_ascii_lowercase()), section),
+            SymbolKey::Unified {
+                file_type,
+                id: KeyId::Debug(ref debug_id),
+            } => unified_path(&id(debug_id.simple().to_string()), file_type),
+            SymbolKey::Unified {
+                file_type,
+                id: KeyId::Code(ref code_id),
+            } => unified_path(&id(code_id.as_str().to_ascii_lowercase()), file_type),
+        }
+    }
+}
+
+impl fmt::Display for SymbolKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(KeyOptions::new()))
+    }
+}
+
+impl str::FromStr for SymbolKey {
+    type Err = ParseKeyError;
+
+    fn from_str(string: &str) -> Result<Self, ParseKeyError> {
+        Self::parse(string)
+    }
+}
+
+/// Returns the file name of a path, which may use forward slashes or backslashes.
+///
+/// Debug file names in PE files and minidumps are often absolute Windows paths, while symbol
+/// server keys only contain the file name.
+fn file_name(path: &str) -> &str {
+    path.rsplit(|c| c == '/' || c == '\\')
+        .next()
+        .unwrap_or(path)
+}
+
+/// Returns the file name without the given extension, which is matched case-insensitively.
+fn strip_extension<'a>(name: &'a str, extension: &str) -> Option<&'a str> {
+    let index = name.len().checked_sub(extension.len())?;
+    if name.is_char_boundary(index) && name[index..].eq_ignore_ascii_case(extension) {
+        Some(&name[..index])
+    } else {
+        None
+    }
+}
+
+/// Returns whether a byte is an unreserved character of URLs as defined by RFC 3986.
+fn is_unreserved(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'-' | b'.' | b'_' | b'~')
+}
+
+/// Returns whether a byte may appear in a URL path or object store key without encoding.
+fn is_url_safe(c: u8) -> bool {
+    is_unreserved(c) || c == b'+'
+}
+
+/// Percent-encodes all bytes of the string for which `is_safe` returns `false`.
+///
+/// The dot-segments `.` and `..` are encoded entirely, since they alter the meaning of paths.
+fn percent_encode(string: &str, is_safe: fn(u8) -> bool) -> String {
+    let is_dot_segment = matches!(string, "." | "..");
+    let mut encoded = String::with_capacity(string.len());
+    for c in string.bytes() {
+        if is_safe(c) && !is_dot_segment {
+            encoded.push(c as char);
+        } else {
+            // Writing into a `String` cannot fail.
+            write!(encoded, "%{:02X}", c).ok();
+        }
+    }
+    encoded
+}
+
+/// Decodes a percent-encoded string located at `offset` in a key.
+fn percent_decode(string: &str, offset: usize) -> Result<String, ParseKeyError> {
+    let bytes = string.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] != b'%' {
+            decoded.push(bytes[index]);
+            index += 1;
+            continue;
+        }
+
+        let byte = string
+            .get(index + 1..index + 3)
+            .filter(|hex| hex.bytes().all(|c| c.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match byte {
+            Some(byte) => decoded.push(byte),
+            None => {
+                return Err(ParseKeyError::new(
+                    ParseErrorKind::InvalidFormat,
+                    offset + index,
+                ))
+            }
+        }
+        index += 3;
+    }
+
+    String::from_utf8(decoded)
+        .map_err(|_| ParseKeyError::new(ParseErrorKind::InvalidFormat, offset))
+}
+
+/// Returns whether the identifier in a key uses the case of symstore keys.
+///
+/// SSQP keys are entirely lowercase, so identifiers with a lowercase letter belong to SSQP keys.
+/// Identifiers without letters are ambiguous and are attributed to symstore, which retains the
+/// case of the file name.
+fn is_symstore_case(id: &str) -> bool {
+    !id.bytes().any(|c| c.is_ascii_lowercase())
+}
+
+/// Parses the components of a key, along with the SSQP kind of code file keys.
+fn parse_key_components(key: &str) -> Result<(&str, KeyId, Option<SsqpKind>), ParseKeyError> {
+    let error = ParseKeyError::new;
+    if key.is_empty() {
+        return Err(error(ParseErrorKind::Empty, 0));
+    }
+
+    let mut components = key.splitn(3, '/');
+    let name = components.next().unwrap_or_default();
+    let id = components.next().unwrap_or_default();
+    let last = components.next().unwrap_or_default();
+
+    let id_offset = name.len() + 1;
+    let last_offset = id_offset + id.len() + 1;
+    if name.is_empty() {
+        return Err(error(ParseErrorKind::InvalidFormat, 0));
+    } else if id.is_empty() || last.is_empty() {
+        return Err(error(
+            ParseErrorKind::InvalidFormat,
+            key.len().min(last_offset),
+        ));
+    } else if let Some(index) = last.find('/') {
+        return Err(error(ParseErrorKind::TrailingGarbage, last_offset + index));
+    }
+
+    let is_debug_file = [".pdb", ".pd_", ".sym"]
+        .iter()
+        .any(|extension| strip_extension(last, extension).is_some());
+
+    if is_debug_file {
+        let result = DebugId::parse_str(id, ParseOptions::breakpad());
+        return match map_offsets(result, |o| o + id_offset) {
+            Ok((debug_id, _)) => Ok((name, KeyId::Debug(debug_id), None)),
+            Err(e) => Err(error(e.kind(), e.offset())),
+        };
+    }
+
+    let prefixes = [
+        (
+            "elf-buildid-sym-",
+            SsqpKind::ElfSymbols,
+            CodeIdKind::GnuBuildId,
+        ),
+        ("elf-buildid-", SsqpKind::ElfBinary, CodeIdKind::GnuBuildId),
+        (
+            "mach-uuid-sym-",
+            SsqpKind::MachSymbols,
+            CodeIdKind::MachUuid,
+        ),
+        ("mach-uuid-", SsqpKind::MachBinary, CodeIdKind::MachUuid),
+    ];
+
+    for &(prefix, ssqp_kind, kind) in &prefixes {
+        if let Some(hex) = id.strip_prefix(prefix) {
+            let hex_offset = id_offset + prefix.len();
+            return match CodeId::parse_hex(hex) {
+                Ok(code_id) => Ok((name, KeyId::Code(code_id.with_kind(kind)), Some(ssqp_kind))),
+                Err(e) => Err(error(e.kind(), hex_offset + e.offset())),
+            };
+        }
+    }
+
+    match CodeId::parse_pe(id) {
+        Ok((timestamp, size_of_image)) => Ok((
+            name,
+            KeyId::Code(CodeId::from_pe(timestamp, size_of_image)),
+            Some(SsqpKind::PeBinary),
+        )),
+        Err(e) => Err(error(e.kind(), id_offset + e.offset())),
+    }
+}
+
+/// Formats the identifier of a PE file as written by `symstore.exe`.
+fn pe_file_id(code_id: &CodeId) -> Option<String> {
+    let (timestamp, size_of_image) = CodeId::parse_pe(code_id.as_str()).ok()?;
+    Some(format!("{:08X}{:x}", timestamp, size_of_image))
+}
+
+/// Builds the path of a Breakpad symbol file from the file name and breakpad identifier.
+fn sym_path(name: &str, id: &str) -> String {
+    let stem = strip_extension(name, ".pdb").unwrap_or(name);
+    format!("{}/{}/{}.sym", name, id, stem)
+}
+
+/// Builds the SSQP key of a code file from the file name and code identifier.
+fn ssqp_code_key(kind: SsqpKind, name: &str, id: &str) -> String {
+    match kind {
+        SsqpKind::ElfBinary => format!("{}/elf-buildid-{}/{}", name, id, name),
+        SsqpKind::ElfSymbols => format!("_.debug/elf-buildid-sym-{}/_.debug", id),
+        SsqpKind::MachBinary => format!("{}/mach-uuid-{}/{}", name, id, name),
+        SsqpKind::MachSymbols => format!("_.dwarf/mach-uuid-sym-{}/_.dwarf", id),
+        SsqpKind::PeBinary => format!("{}/{}/{}", name, id, name),
+    }
+}
+
+/// Builds the path of a file on a debuginfod server from the build id.
+fn debuginfod_path(id: &str, kind: DebuginfodKind) -> String {
+    let kind = match kind {
+        DebuginfodKind::Debuginfo => "debuginfo",
+        DebuginfodKind::Executable => "executable",
+    };
+
+    format!("buildid/{}/{}", id, kind)
+}
+
+/// Builds the path of an ELF section on a debuginfod server from the build id.
+fn debuginfod_section_path(id: &str, section: &str) -> String {
+    format!(
+        "buildid/{}/section/{}",
+        id,
+        percent_encode(section, is_unreserved)
+    )
+}
+
+/// Builds a path in the unified layout from the lowercase identifier.
+fn unified_path(id: &str, file_type: UnifiedFileType) -> String {
+    let file_type = match file_type {
+        UnifiedFileType::Debuginfo => "debuginfo",
+        UnifiedFileType::Executable => "executable",
+        UnifiedFileType::SourceBundle => "sourcebundle",
+    };
+
+    // Preserved code identifiers may contain arbitrary characters, so split at a char boundary.
+    let split = id
+        .char_indices()
+        .nth(2)
+        .map_or(id.len(), |(index, _)| index);
+    let (prefix, rest) = id.split_at(split);
+    format!("{}/{}/{}", prefix, rest, file_type)
+}
@@ -0,0 +1,147 @@
+use std::str::FromStr;
+
+use debugid::breakpad::{CodeIdRecord, ModuleRecord};
+use debugid::{CodeId, DebugId, ParseErrorKind};
+
+#[test]
+fn test_module_record() {
+    let record =
+        ModuleRecord::parse("MODULE Linux x86_64 DFB8E43AF2423D73A453AEB6A777EF750 libfoo.so")
+            .unwrap();
+
+    assert_eq!(
+        record,
+        ModuleRecord {
+            os: "Linux".into(),
+            arch: "x86_64".into(),
+            debug_id: DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap(),
+            name: "libfoo.so".into(),
+        }
+    );
+}
+
+#[test]
+fn test_module_record_whitespace() {
+    let record = ModuleRecord::parse(
+        "MODULE  windows x86   DFB8E43AF2423D73A453AEB6A777EF75a My App.pdb\r\n",
+    )
+    .unwrap();
+
+    assert_eq!(record.os, "windows");
+    assert_eq!(record.arch, "x86");
+    assert_eq!(record.debug_id.appendix(), 10);
+    assert_eq!(record.name, "My App.pdb");
+}
+
+#[test]
+fn test_module_record_roundtrip() {
+    let line = "MODULE mac arm64 DFB8E43AF2423D73A453AEB6A777EF75a Foo Framework";
+    let record: ModuleRecord = line.parse().unwrap();
+    assert_eq!(record.to_string(), line);
+
+    let record = ModuleRecord::parse("MODULE windows x86 418E89C31 old.pdb").unwrap();
+    assert!(record.debug_id.is_pdb20());
+    assert_eq!(record.to_string(), "MODULE windows x86 418E89C31 old.pdb");
+}
+
+#[test]
+fn test_module_record_errors() {
+    let error = ModuleRecord::parse("").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::Empty);
+
+    let error = ModuleRecord::parse("INFO CODE_ID 5CCC38584B08000").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 0)
+    );
+
+    let error = ModuleRecord::parse("MODULE Linux x86_64").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 19)
+    );
+
+    let error =
+        ModuleRecord::parse("MODULE Linux x86_64 DFB8E43AF2423D73A453AEB6A777EF750").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 53)
+    );
+
+    let error =
+        ModuleRecord::parse("MODULE Linux x86_64 DFB8E43AF2423D73A453AEB6A777EF7X0 libfoo.so")
+            .unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 51)
+    );
+    assert_eq!(
+        error.to_string(),
+        "invalid breakpad record \"MODULE Linux x86_64 DFB8E43AF2423D73A453AEB6A777\"...: \
+         invalid hex digit at offset 51"
+    );
+    assert!(error.is_input_truncated());
+
+    let error =
+        ModuleRecord::parse("MODULE Linux x86_64 dfb8e43a-f242-3d73-a453-aeb6a777ef75 libfoo.so")
+            .unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 28)
+    );
+}
+
+#[test]
+fn test_code_id_record() {
+    let record = CodeIdRecord::parse("INFO CODE_ID 5CCC38584B08000 ntdll.dll\n").unwrap();
+    assert_eq!(
+        record,
+        CodeIdRecord {
+            code_id: CodeId::new("5ccc38584b08000".into()),
+            code_file: Some("ntdll.dll".into()),
+        }
+    );
+
+    let record =
+        CodeIdRecord::parse("INFO CODE_ID 3AE4B8DF42F2733DA453AEB6A777EF7593A66D4F").unwrap();
+    assert_eq!(record.code_file, None);
+    assert_eq!(
+        record.to_string(),
+        "INFO CODE_ID 3AE4B8DF42F2733DA453AEB6A777EF7593A66D4F"
+    );
+}
+
+#[test]
+fn test_code_id_record_roundtrip() {
+    let line = "INFO CODE_ID 5CCC38584B08000 My Library.dll";
+    let record: CodeIdRecord = line.parse().unwrap();
+    assert_eq!(record.code_file.as_deref(), Some("My Library.dll"));
+    assert_eq!(record.to_string(), line);
+}
+
+#[test]
+fn test_code_id_record_errors() {
+    let error = CodeIdRecord::parse("INFO GENERATOR foo").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 5)
+    );
+
+    let error = CodeIdRecord::parse("MODULE CODE_ID 5CCC38584B08000").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 0)
+    );
+
+    let error = CodeIdRecord::parse("INFO CODE_ID").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 12)
+    );
+
+    let error = CodeIdRecord::parse("INFO CODE_ID 5CCC3858-4B08000").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 21)
+    );
+}
@@ -1,4 +1,10 @@
-use debugid::CodeId;
+use std::convert::TryFrom;
+
+use debugid::{
+    CodeId, CodeIdArc, CodeIdKind, CodeIdRef, DebugId, FatCodeId, FileFormat, FixedCodeId,
+    GnuBuildId, ParseErrorKind, PeCodeId,
+};
+use uuid::Uuid;
 
 #[test]
 fn test_new() {
@@ -25,3 +31,746 @@ fn test_is_nil() {
     let id = CodeId::nil();
     assert!(id.is_nil());
 }
+
+#[test]
+fn test_kind() {
+    let id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    assert_eq!(id.kind(), CodeIdKind::Opaque);
+
+    let id = id.with_kind(CodeIdKind::MachUuid);
+    assert_eq!(id.kind(), CodeIdKind::MachUuid);
+
+    // The kind is not considered in comparisons.
+    assert_eq!(id, CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into()));
+}
+
+#[test]
+fn test_from_pe() {
+    let id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert_eq!(id.as_str(), "5ccc38584b08000");
+    assert_eq!(id.kind(), CodeIdKind::PeTimestampSize);
+
+    // The timestamp is zero-padded, the size of image is not.
+    let id = CodeId::from_pe(0x1234, 0x1000);
+    assert_eq!(id.as_str(), "000012341000");
+}
+
+#[test]
+fn test_pe_parts() {
+    let id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert_eq!(id.timestamp(), Some(0x5ccc_3858));
+    assert_eq!(id.size_of_image(), Some(0x04b0_8000));
+
+    let id = CodeId::new("5ccc38584b08000".into());
+    assert_eq!(id.timestamp(), Some(0x5ccc_3858));
+    assert_eq!(id.size_of_image(), Some(0x04b0_8000));
+
+    let id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    assert_eq!(id.timestamp(), None);
+    assert_eq!(id.size_of_image(), None);
+
+    let id = CodeId::new("5ccc38584b08000".into()).with_kind(CodeIdKind::GnuBuildId);
+    assert_eq!(id.timestamp(), None);
+}
+
+#[test]
+fn test_uuid() {
+    let id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    assert_eq!(
+        id.uuid(),
+        Some(Uuid::parse_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap())
+    );
+
+    let id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75feedface".into());
+    assert_eq!(id.uuid(), None);
+
+    let id =
+        CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into()).with_kind(CodeIdKind::GnuBuildId);
+    assert_eq!(id.uuid(), None);
+}
+
+#[test]
+fn test_parse_hex() {
+    let id = CodeId::parse_hex("5CCC38584b08000").unwrap();
+    assert_eq!(id.as_str(), "5ccc38584b08000");
+
+    assert!(CodeId::parse_hex("").is_err());
+    assert!(CodeId::parse_hex("dfb8e43a-f242-3d73").is_err());
+    assert!(CodeId::parse_hex("dfb8e43af2423d73 ").is_err());
+    assert!(CodeId::parse_hex("xyz").is_err());
+}
+
+#[test]
+fn test_parse_hex_error() {
+    let error = CodeId::parse_hex("").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::Empty);
+    assert_eq!(error.to_string(), "invalid code identifier: empty input");
+
+    let error = CodeId::parse_hex("dfb8e43a-f242-3d73").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidHex);
+    assert_eq!(error.offset(), 8);
+    assert_eq!(
+        error.to_string(),
+        "invalid code identifier \"dfb8e43a-f242-3d73\": invalid hex digit at offset 8"
+    );
+
+    let error = CodeId::parse_hex(&"ab".repeat(65)).unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+    assert_eq!(error.offset(), CodeId::MAX_HEX_LEN);
+
+    let error = CodeId::new("5ccc38584b08000".into()).bytes().unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+    assert_eq!(error.offset(), 15);
+}
+
+#[test]
+fn test_new_preserving() {
+    let id = CodeId::new_preserving("5CCC38584B08000".into());
+    assert_eq!(id.as_str(), "5CCC38584B08000");
+    assert_eq!(id.to_string(), "5CCC38584B08000");
+    assert_eq!(id.timestamp(), Some(0x5ccc_3858));
+    assert_ne!(id, CodeId::new("5CCC38584B08000".into()));
+}
+
+#[test]
+fn test_long() {
+    // Long identifiers no longer fit into inline storage.
+    let hex = "dfb8e43af2423d73a453aeb6a777ef75".repeat(3);
+
+    assert_eq!(CodeId::new(hex.clone()).as_str(), hex);
+    assert_eq!(CodeId::parse_hex(&hex).unwrap().as_str(), hex);
+
+    let binary = [0xab; 48];
+    assert_eq!(CodeId::from_binary(&binary).as_str(), "ab".repeat(48));
+}
+
+#[test]
+fn test_bytes() {
+    let binary = b"\xdf\xb8\xe4\x3a\xf2\x42\x3d\x73\xa4\x53\xae\xb6\xa7\x77\xef\x75";
+    let id = CodeId::from_binary(&binary[..]);
+    assert_eq!(id.bytes().unwrap(), binary);
+
+    assert_eq!(CodeId::nil().bytes().unwrap(), b"");
+    assert!(CodeId::new("5ccc38584b08000".into()).bytes().is_err());
+    assert!(CodeId::new_preserving("dfb8-e43a".into()).bytes().is_err());
+}
+
+#[test]
+fn test_validate_for() {
+    let pe = CodeId::new("5ccc38584b08000".into());
+    let mach = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    let elf = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75feedface".into());
+
+    assert!(pe.validate_for(FileFormat::Pe).is_ok());
+    assert!(pe.validate_for(FileFormat::Elf).is_err());
+    assert!(pe.validate_for(FileFormat::MachO).is_err());
+
+    assert!(mach.validate_for(FileFormat::MachO).is_ok());
+    assert!(mach.validate_for(FileFormat::Elf).is_ok());
+    assert!(mach.validate_for(FileFormat::Pe).is_err());
+
+    assert!(elf.validate_for(FileFormat::Elf).is_ok());
+    assert!(elf.validate_for(FileFormat::Wasm).is_ok());
+    assert!(elf.validate_for(FileFormat::MachO).is_err());
+
+    assert!(CodeId::nil().validate_for(FileFormat::Wasm).is_err());
+    assert!(CodeId::new("dfb8e43a".into())
+        .validate_for(FileFormat::Elf)
+        .is_err());
+}
+
+#[test]
+fn test_uppercase() {
+    let id = CodeId::new("5ccc38584b08000".into());
+    assert_eq!(id.uppercase().to_string(), "5CCC38584B08000");
+    assert_eq!(id.as_str(), "5ccc38584b08000");
+}
+
+#[test]
+fn test_from_elf_note() {
+    let mut note = Vec::new();
+    note.extend_from_slice(&4u32.to_le_bytes());
+    note.extend_from_slice(&20u32.to_le_bytes());
+    note.extend_from_slice(&3u32.to_le_bytes());
+    note.extend_from_slice(b"GNU\0");
+    note.extend_from_slice(b"\xdf\xb8\xe4\x3a\xf2\x42\x3d\x73\xa4\x53\xae\xb6\xa7\x77\xef\x75");
+    note.extend_from_slice(b"\xfe\xed\xfa\xce");
+
+    let id = CodeId::from_elf_note(&note).unwrap();
+    assert_eq!(id.as_str(), "dfb8e43af2423d73a453aeb6a777ef75feedface");
+    assert_eq!(id.kind(), CodeIdKind::GnuBuildId);
+}
+
+#[test]
+fn test_from_elf_note_big_endian() {
+    // A preceding note of a different type with padding, followed by the build id.
+    let mut note = Vec::new();
+    note.extend_from_slice(&4u32.to_be_bytes());
+    note.extend_from_slice(&3u32.to_be_bytes());
+    note.extend_from_slice(&1u32.to_be_bytes());
+    note.extend_from_slice(b"GNU\0");
+    note.extend_from_slice(b"\x01\x02\x03\x00");
+    note.extend_from_slice(&4u32.to_be_bytes());
+    note.extend_from_slice(&8u32.to_be_bytes());
+    note.extend_from_slice(&3u32.to_be_bytes());
+    note.extend_from_slice(b"GNU\0");
+    note.extend_from_slice(b"\xdf\xb8\xe4\x3a\xf2\x42\x3d\x73");
+
+    let id = CodeId::from_elf_note(&note).unwrap();
+    assert_eq!(id.as_str(), "dfb8e43af2423d73");
+}
+
+#[test]
+fn test_from_elf_note_error() {
+    assert!(CodeId::from_elf_note(b"").is_err());
+    assert!(
+        CodeId::from_elf_note(b"\x04\x00\x00\x00\x14\x00\x00\x00\x03\x00\x00\x00GNU\0").is_err()
+    );
+}
+
+#[test]
+fn test_from_mach_uuid() {
+    let uuid = Uuid::parse_str("DFB8E43A-F242-3D73-A453-AEB6A777EF75").unwrap();
+    let id = CodeId::from_mach_uuid(uuid);
+
+    assert_eq!(id.as_str(), "dfb8e43af2423d73a453aeb6a777ef75");
+    assert_eq!(id.kind(), CodeIdKind::MachUuid);
+    assert_eq!(id.uuid(), Some(uuid));
+}
+
+#[test]
+fn test_from_wasm_build_id() {
+    let id = CodeId::from_wasm_build_id(b"\xdf\xb8\xe4\x3a\xf2\x42\x3d\x73");
+    assert_eq!(id.as_str(), "dfb8e43af2423d73");
+    assert_eq!(id.kind(), CodeIdKind::WasmBuildId);
+}
+
+#[test]
+fn test_to_debug_id() {
+    let id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75feedface".into());
+    assert_eq!(
+        id.to_debug_id(FileFormat::Elf).unwrap(),
+        "3ae4b8df-42f2-733d-a453-aeb6a777ef75"
+            .parse::<DebugId>()
+            .unwrap()
+    );
+
+    let id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    assert_eq!(
+        id.to_debug_id(FileFormat::MachO).unwrap(),
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75"
+            .parse::<DebugId>()
+            .unwrap()
+    );
+
+    let id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert!(id.to_debug_id(FileFormat::Pe).is_err());
+}
+
+#[test]
+fn test_sort_key() {
+    let mut ids = [
+        CodeId::from_binary(&[0x01; 20]).with_kind(CodeIdKind::GnuBuildId),
+        CodeId::from_pe(0x5ccc_3858, 0x04b0_8000),
+        CodeId::from_binary(&[0x02; 8]).with_kind(CodeIdKind::GnuBuildId),
+        CodeId::from_pe(0x0000_0001, 0x1000),
+    ];
+    ids.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+
+    let sorted: Vec<_> = ids.iter().map(CodeId::as_str).collect();
+    assert_eq!(
+        sorted,
+        [
+            "000000011000",
+            "5ccc38584b08000",
+            "0202020202020202",
+            "0101010101010101010101010101010101010101",
+        ]
+    );
+}
+
+#[test]
+fn test_eq_normalized() {
+    let short = CodeId::new("dfb8e43af2423d73".into());
+    let padded = CodeId::new("dfb8e43af2423d730000000000000000".into());
+    assert!(short.eq_normalized(&padded));
+    assert!(padded.eq_normalized(&short));
+    assert!(short.eq_normalized(&CodeId::new_preserving("DFB8E43AF2423D73".into())));
+
+    let other = CodeId::new("dfb8e43af2423d7300000000000000ff".into());
+    assert!(!short.eq_normalized(&other));
+
+    // Odd-length identifiers are never padded.
+    let pe = CodeId::new("5ccc38584b08000".into());
+    assert!(!pe.eq_normalized(&CodeId::new("5ccc38584b08".into())));
+}
+
+#[test]
+fn test_fixed() {
+    let id = FixedCodeId::new("DFB8E43AF2423D73A453AEB6A777EF75feedface").unwrap();
+    assert_eq!(id.as_str(), "dfb8e43af2423d73a453aeb6a777ef75feedface");
+    assert_eq!(
+        CodeId::from(id),
+        CodeId::new("dfb8e43af2423d73a453aeb6a777ef75feedface".into())
+    );
+
+    let code_id = CodeId::new("5ccc38584b08000".into());
+    assert_eq!(
+        FixedCodeId::try_from(&code_id).unwrap().to_string(),
+        "5ccc38584b08000"
+    );
+
+    assert!(FixedCodeId::nil().is_nil());
+    assert_eq!(
+        FixedCodeId::new("").unwrap_err().kind(),
+        CodeId::parse_hex("").unwrap_err().kind()
+    );
+    assert!(FixedCodeId::new("dfb8-e43a").is_err());
+    assert!(FixedCodeId::new(&"a".repeat(FixedCodeId::CAPACITY + 1)).is_err());
+    assert!(FixedCodeId::from_binary(&[0; FixedCodeId::CAPACITY / 2]).is_ok());
+    assert!(FixedCodeId::from_binary(&[0; FixedCodeId::CAPACITY / 2 + 1]).is_err());
+}
+
+#[test]
+fn test_parse_pe() {
+    assert_eq!(
+        CodeId::parse_pe("5eb8679a17b000"),
+        Ok((0x5eb8_679a, 0x0017_b000))
+    );
+    assert_eq!(CodeId::parse_pe("5EB8679A1"), Ok((0x5eb8_679a, 1)));
+    assert_eq!(
+        CodeId::parse_pe("5eb8679affffffff"),
+        Ok((0x5eb8_679a, 0xffff_ffff))
+    );
+
+    let error = CodeId::parse_pe("5eb8679a").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+    assert_eq!(
+        CodeId::parse_pe("5eb8679a-17b000").unwrap_err().kind(),
+        ParseErrorKind::InvalidHex
+    );
+    assert!(CodeId::parse_pe("5eb8679a1ffffffff").is_err());
+}
+
+#[test]
+fn test_truncated() {
+    let build_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75c8f1e4a3".into());
+    let prefix = build_id.truncated(16);
+    assert_eq!(prefix.as_str(), "dfb8e43af2423d73a453aeb6a777ef75");
+    assert_eq!(prefix.to_code_id(), prefix);
+    assert_eq!(
+        DebugId::try_from_code_id(&prefix.to_code_id(), FileFormat::Elf),
+        DebugId::try_from_code_id(&build_id, FileFormat::Elf)
+    );
+
+    assert_eq!(build_id.truncated(64), build_id);
+    assert_eq!(build_id.truncated(usize::MAX), build_id);
+    assert!(build_id.truncated(0).is_nil());
+    assert_eq!(CodeIdRef::from(&build_id), build_id);
+}
+
+#[test]
+fn test_from_text() {
+    let id = CodeId::from_text("base.apk:Xk3_Lm-9").unwrap();
+    assert_eq!(id.as_str(), "base.apk:Xk3_Lm-9");
+    assert_eq!(id.kind(), CodeIdKind::Text);
+    assert!(id.bytes().is_err());
+    assert_eq!(id.timestamp(), None);
+
+    // Hex strings are not normalized either.
+    assert_eq!(CodeId::from_text("ABCDEF").unwrap().as_str(), "ABCDEF");
+
+    let error = CodeId::from_text("").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::Empty);
+
+    let error = CodeId::from_text("abc def").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidFormat);
+    assert_eq!(error.offset(), 3);
+    assert!(CodeId::from_text("abc\u{e9}").is_err());
+}
+
+#[test]
+fn test_debuglink_crc() {
+    let id = CodeId::from_debuglink_crc(0x0000_2c3d);
+    assert_eq!(id.as_str(), "00002c3d");
+    assert_eq!(id.kind(), CodeIdKind::DebugLinkCrc);
+    assert_eq!(id.debuglink_crc(), Some(0x2c3d));
+    assert_eq!(id.timestamp(), None);
+
+    let id = CodeId::new("00002c3d".into());
+    assert_eq!(id.debuglink_crc(), None);
+    assert_eq!(
+        id.with_kind(CodeIdKind::DebugLinkCrc).debuglink_crc(),
+        Some(0x2c3d)
+    );
+}
+
+#[test]
+fn test_eq_str_and_bytes() {
+    let id = CodeId::new("dfb8e43af2423d73".into());
+    assert_eq!(id, "dfb8e43af2423d73");
+    assert_ne!(id, *"DFB8E43AF2423D73");
+    assert_eq!("dfb8e43af2423d73", id);
+    assert_ne!(id, "dfb8e43a-f242-3d73");
+    assert_ne!(id, "dfb8e43af2423d");
+
+    let bytes: &[u8] = b"\xdf\xb8\xe4\x3a\xf2\x42\x3d\x73";
+    assert_eq!(id, bytes);
+    assert_eq!(*bytes, id);
+    assert_ne!(id, &bytes[..7]);
+    assert_ne!(CodeId::new("5ccc38584b08000".into()), &[0x5c; 7][..]);
+    assert_ne!(CodeId::new_preserving("dfb8e43af2423d7x".into()), bytes);
+
+    // Comparisons with strings and bytes are consistent with comparing code identifiers.
+    let upper = CodeId::new_preserving("DFB8E43AF2423D73".into());
+    assert_ne!(upper, id);
+    assert_eq!(upper, "DFB8E43AF2423D73");
+    assert_ne!(upper, "dfb8e43af2423d73");
+    assert_ne!(upper, bytes);
+    assert!(upper.eq_normalized(&id));
+}
+
+#[test]
+fn test_arc() {
+    let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    let shared = CodeIdArc::from(&code_id);
+    assert_eq!(shared, code_id);
+    assert_eq!(shared.as_str(), "5ccc38584b08000");
+    assert_eq!(shared.kind(), CodeIdKind::PeTimestampSize);
+    assert_eq!(shared.to_string(), code_id.to_string());
+    assert_eq!(format!("{:?}", shared), "CodeIdArc(5ccc38584b08000)");
+    assert_eq!(
+        CodeId::from(shared.clone()).kind(),
+        CodeIdKind::PeTimestampSize
+    );
+    assert!(CodeIdArc::default().is_nil());
+}
+
+#[test]
+fn test_chunked() {
+    let id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert_eq!(id.chunked(4, '-').to_string(), "5ccc-3858-4b08-000");
+    assert_eq!(id.chunked(8, ' ').to_string(), "5ccc3858 4b08000");
+    assert_eq!(id.chunked(0, ' ').to_string(), "5ccc38584b08000");
+    assert_eq!(id.chunked(15, ' ').to_string(), "5ccc38584b08000");
+    assert_eq!(CodeId::nil().chunked(4, '-').to_string(), "");
+}
+
+#[test]
+fn test_len() {
+    let id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert_eq!(id.nibble_count(), 15);
+    assert_eq!(id.len_bytes(), 7);
+
+    let id = CodeId::from_binary(&[0xab; 20]);
+    assert_eq!(id.nibble_count(), 40);
+    assert_eq!(id.len_bytes(), 20);
+
+    assert_eq!(CodeId::nil().nibble_count(), 0);
+    assert_eq!(CodeId::nil().len_bytes(), 0);
+}
+
+#[test]
+fn test_fat() {
+    let x86_64 = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    let arm64 = CodeId::new("2b36e3fc0b5a3e8c9d11c4e6c3d8a0f1".into());
+    let mut fat: FatCodeId = vec![("x86_64", x86_64.clone())].into_iter().collect();
+    assert_eq!(fat.insert("arm64", arm64.clone()), None);
+
+    assert_eq!(fat.len(), 2);
+    assert_eq!(fat.get("x86_64"), Some(&x86_64));
+    assert_eq!(fat.get("i386"), None);
+    assert_eq!(fat.arch_of(&arm64), Some("arm64"));
+    assert!(fat.contains(&x86_64));
+    assert!(!fat.contains(&CodeId::nil()));
+
+    let archs: Vec<_> = fat.iter().map(|(arch, _)| arch).collect();
+    assert_eq!(archs, ["arm64", "x86_64"]);
+    assert!(FatCodeId::new().is_empty());
+}
+
+#[test]
+fn test_go_build_id() {
+    let build_id =
+        "pQx7VHMz_3v5X2qYtB1k/Lr8zWcN9mE4dF6gH0jK2/pQx7VHMz_3v5X2qYtB1k/aB3cD5eF7gH9iJ1kL3mN";
+    let id = CodeId::from_go_build_id(build_id).unwrap();
+    assert_eq!(id.kind(), CodeIdKind::GoBuildId);
+    assert_eq!(id.len_bytes(), build_id.len());
+    assert!(id.as_str().bytes().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(id.go_build_id().as_deref(), Some(build_id));
+
+    // The kind is required to recover the build id.
+    let opaque = CodeId::new(id.as_str().into());
+    assert_eq!(opaque.go_build_id(), None);
+    assert_eq!(
+        opaque
+            .with_kind(CodeIdKind::GoBuildId)
+            .go_build_id()
+            .as_deref(),
+        Some(build_id)
+    );
+
+    assert!(CodeId::from_go_build_id("").is_err());
+    assert_eq!(
+        CodeId::from_go_build_id("abc def").unwrap_err().kind(),
+        ParseErrorKind::InvalidFormat
+    );
+}
+
+#[test]
+fn test_from_ascii() {
+    let id = CodeId::from_ascii(b"DFB8E43AF2423D73").unwrap();
+    assert_eq!(id.as_str(), "dfb8e43af2423d73");
+
+    let error = CodeId::from_ascii(b"dfb8\xe4").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::NonAscii, 4)
+    );
+    assert!(CodeId::from_ascii(b"").is_err());
+}
+
+#[test]
+fn test_parse_many() {
+    let index = "dfb8e43af2423d73a453aeb6a777ef75\n\nDFB8E43A\nxyz";
+    let results: Vec<_> = CodeId::parse_many(index.lines()).collect();
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(
+        results[0].as_ref().unwrap().as_str(),
+        "dfb8e43af2423d73a453aeb6a777ef75"
+    );
+    assert_eq!(
+        results[1].as_ref().unwrap_err().kind(),
+        ParseErrorKind::Empty
+    );
+    assert_eq!(results[2].as_ref().unwrap().as_str(), "dfb8e43a");
+    assert_eq!(
+        results[3].as_ref().unwrap_err().kind(),
+        ParseErrorKind::InvalidHex
+    );
+}
+
+#[test]
+fn test_is_valid_hex() {
+    assert!(CodeId::is_valid_hex("dfb8e43af2423d73a453aeb6a777ef75"));
+    assert!(CodeId::is_valid_hex("DFB8E43A"));
+    assert!(!CodeId::is_valid_hex(""));
+    assert!(!CodeId::is_valid_hex("dfb8e43a-f242"));
+    assert!(!CodeId::is_valid_hex(&"a".repeat(CodeId::MAX_HEX_LEN + 1)));
+}
+
+#[test]
+fn test_pe_code_id() {
+    let pe: PeCodeId = "5CCC38584B08000".parse().unwrap();
+    assert_eq!(pe, PeCodeId::new(0x5ccc_3858, 0x04b0_8000));
+    assert_eq!(pe.to_string(), "5ccc38584b08000");
+
+    let code_id = CodeId::from(pe);
+    assert_eq!(code_id.kind(), CodeIdKind::PeTimestampSize);
+    assert_eq!(PeCodeId::try_from(&code_id), Ok(pe));
+
+    let error = "5ccc3858".parse::<PeCodeId>().unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+    let error = "5ccc3858-4b08000".parse::<PeCodeId>().unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidHex);
+}
+
+#[test]
+fn test_gnu_build_id() {
+    let build_id: GnuBuildId = "DFB8E43AF2423D73A453AEB6A777EF75A0B1C2D3".parse().unwrap();
+    assert_eq!(
+        build_id.as_str(),
+        "dfb8e43af2423d73a453aeb6a777ef75a0b1c2d3"
+    );
+    assert_eq!(build_id.as_code_id().kind(), CodeIdKind::GnuBuildId);
+
+    let code_id = CodeId::from(build_id.clone());
+    assert_eq!(GnuBuildId::try_from(&code_id), Ok(build_id));
+
+    let error = "dfb8e43a".parse::<GnuBuildId>().unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+    let error = "dfb8e43af2423d73a".parse::<GnuBuildId>().unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+    let error = "dfb8e43a-f2423d73".parse::<GnuBuildId>().unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidHex);
+}
+
+#[test]
+fn test_padded() {
+    let code_id = CodeId::new("b8e43af2423d73a".into()).with_kind(CodeIdKind::GnuBuildId);
+    assert_eq!(code_id.padded().to_string(), "0b8e43af2423d73a");
+
+    let code_id = CodeId::new("b8e43af2423d73a".into()).with_kind(CodeIdKind::MachUuid);
+    assert_eq!(code_id.padded().to_string(), "0b8e43af2423d73a");
+
+    let code_id = CodeId::from_binary(&[0xdf, 0xb8]).with_kind(CodeIdKind::GnuBuildId);
+    assert_eq!(code_id.padded().to_string(), "dfb8");
+
+    let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert_eq!(code_id.padded().to_string(), "5ccc38584b08000");
+
+    let code_id = CodeId::new("b8e43af2423d73a".into());
+    assert_eq!(code_id.padded().to_string(), "b8e43af2423d73a");
+}
+
+#[test]
+fn test_parse_non_ascii() {
+    let error = CodeId::parse_hex("dfb8\u{e4}").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::NonAscii);
+    assert_eq!(error.offset(), 4);
+
+    let error = CodeId::from_text("abc\u{e9}").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::NonAscii);
+    let error = CodeId::from_text("abc def").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidFormat);
+
+    let error = CodeId::from_go_build_id("Xk3_\u{e9}").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::NonAscii);
+
+    let error = CodeId::new_preserving("dfb8\u{e4}".into())
+        .bytes()
+        .unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::NonAscii);
+}
+
+#[test]
+fn test_parse_build_id_line() {
+    let expected = "3ae4b8df42f2733da453aeb6a777ef7593a66d4f";
+
+    let lines = [
+        "    Build ID: 3ae4b8df42f2733da453aeb6a777ef7593a66d4f",
+        "    Build ID: 3AE4B8DF42F2733DA453AEB6A777EF7593A66D4F\n",
+        "/usr/bin/foo: ELF 64-bit LSB pie executable, x86-64, version 1 (SYSV), dynamically linked, \
+         BuildID[sha1]=3ae4b8df42f2733da453aeb6a777ef7593a66d4f, for GNU/Linux 3.2.0, stripped",
+        "0x400000+0x1000 3ae4b8df42f2733da453aeb6a777ef7593a66d4f@0x400284 /usr/bin/foo - foo",
+    ];
+
+    for line in &lines {
+        let code_id = CodeId::parse_build_id_line(line).unwrap();
+        assert_eq!(code_id.as_str(), expected, "{}", line);
+        assert_eq!(code_id.kind(), CodeIdKind::GnuBuildId);
+    }
+
+    let error = CodeId::parse_build_id_line("").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::Empty);
+
+    let error =
+        CodeId::parse_build_id_line("Displaying notes found in: .note.gnu.build-id").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 0)
+    );
+
+    let error = CodeId::parse_build_id_line("    Build ID: 3ae4b8dx").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 21)
+    );
+
+    let error = CodeId::parse_build_id_line("0x400000+0x1000 - /usr/bin/foo - foo").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 16)
+    );
+}
+
+#[test]
+fn test_shard_prefix() {
+    let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    assert_eq!(code_id.shard_prefix(0), "");
+    assert_eq!(code_id.shard_prefix(4), "3ae4");
+    assert_eq!(
+        code_id.shard_prefix(100),
+        "3ae4b8df42f2733da453aeb6a777ef7593a66d4f"
+    );
+}
+
+#[test]
+fn test_uppercase_padded() {
+    let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert_eq!(
+        format!("{:>20}|", code_id.uppercase()),
+        "     5CCC38584B08000|"
+    );
+    assert_eq!(
+        format!("{:-<20}|", code_id.uppercase()),
+        "5CCC38584B08000-----|"
+    );
+
+    // Identifiers longer than the inline buffer are padded as well.
+    let code_id = CodeId::new("ab".repeat(40));
+    assert_eq!(
+        format!("{:>81}", code_id.uppercase()),
+        format!(" {}", "AB".repeat(40))
+    );
+}
+
+#[test]
+fn test_chunked_padded() {
+    let id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert_eq!(
+        format!("{:>20}|", id.chunked(4, '-')),
+        "  5ccc-3858-4b08-000|"
+    );
+    assert_eq!(
+        format!("{:^20}|", id.chunked(8, ' ')),
+        "  5ccc3858 4b08000  |"
+    );
+
+    // Grouped output longer than the inline buffer is padded as well.
+    let id = CodeId::new("ab".repeat(40));
+    assert_eq!(format!("{:>100}", id.chunked(8, ' ')).len(), 100);
+}
+
+#[test]
+fn test_padded_width() {
+    let code_id = CodeId::new("b8e43af2423d73a".into()).with_kind(CodeIdKind::GnuBuildId);
+    assert_eq!(
+        format!("{:>20}|", code_id.padded()),
+        "    0b8e43af2423d73a|"
+    );
+    assert_eq!(
+        format!("{:*<20}|", code_id.padded()),
+        "0b8e43af2423d73a****|"
+    );
+
+    // Identifiers longer than the inline buffer are padded as well.
+    let code_id = CodeId::new("b".repeat(79)).with_kind(CodeIdKind::GnuBuildId);
+    assert_eq!(
+        format!("{:>81}", code_id.padded()),
+        format!(" 0{}", "b".repeat(79))
+    );
+}
+
+#[test]
+fn test_fixed_padded() {
+    let id = FixedCodeId::new("5ccc38584b08000").unwrap();
+    assert_eq!(format!("{:>20}|", id), "     5ccc38584b08000|");
+    assert_eq!(format!("{:-<20}|", id), "5ccc38584b08000-----|");
+}
+
+#[test]
+fn test_size() {
+    // SHA-1 GNU build ids are stored inline, while keeping the type small.
+    assert!(std::mem::size_of::<CodeId>() <= 56);
+    assert_eq!(
+        std::mem::size_of::<Option<CodeId>>(),
+        std::mem::size_of::<CodeId>()
+    );
+}
+
+#[test]
+fn test_inline_boundary() {
+    let sha1 = "ab".repeat(20);
+    assert_eq!(CodeId::parse_hex(&sha1).unwrap().as_str(), sha1);
+    assert_eq!(CodeId::from_binary(&[0xab; 20]).as_str(), sha1);
+
+    let long = "ab".repeat(21);
+    assert_eq!(CodeId::parse_hex(&long).unwrap().as_str(), long);
+    assert_eq!(CodeId::from_binary(&[0xab; 21]).as_str(), long);
+    assert_eq!(CodeId::new(long.to_uppercase()).as_str(), long);
+
+    let fixed = FixedCodeId::new(&"cd".repeat(24)).unwrap();
+    assert_eq!(CodeId::from(fixed).as_str(), "cd".repeat(24));
+}
@@ -1,7 +1,11 @@
+use std::convert::TryFrom;
 use std::mem::{align_of, size_of};
 use std::str::FromStr;
 
-use debugid::DebugId;
+use debugid::{
+    match_module, minidump, namespace, AppendixMode, CodeId, DebugId, DebugIdFormat, DebugIdStyle,
+    FileFormat, MatchQuality, NonNilDebugId, ParseErrorKind, ParseOptions, Pdb20Policy,
+};
 use uuid::Uuid;
 
 #[test]
@@ -123,6 +127,734 @@ fn test_parse_error_unicode() {
     assert!(DebugId::from_str("아이쿱 조합원 앱카드").is_err());
 }
 
+#[test]
+fn test_parse_error_kind() {
+    let assert_error = |input: &str, kind, offset| {
+        let error = DebugId::from_str(input).unwrap_err();
+        assert_eq!((error.kind(), error.offset()), (kind, offset), "{}", input);
+    };
+
+    assert_error("", ParseErrorKind::Empty, 0);
+    assert_error(
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef7",
+        ParseErrorKind::InvalidLength,
+        35,
+    );
+    assert_error(
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-",
+        ParseErrorKind::MissingAppendix,
+        37,
+    );
+    assert_error(
+        "dfb8e43a-f242-3d73-a453-aeb6a7x7ef75",
+        ParseErrorKind::InvalidHex,
+        30,
+    );
+    assert_error(
+        "dfb8e43a-f2423d73-a453-aeb6a777ef75",
+        ParseErrorKind::InvalidFormat,
+        13,
+    );
+    assert_error(
+        "dfb8e43af2423d73a453aeb6a777ef75-a",
+        ParseErrorKind::InvalidFormat,
+        32,
+    );
+    assert_error("5ccc3858x", ParseErrorKind::InvalidHex, 8);
+    assert_error("아이쿱 조합원 앱카드", ParseErrorKind::NonAscii, 0);
+
+    let error = DebugId::from_breakpad("DFB8E43AF2423D73A453AEB6A777EF75").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::MissingAppendix);
+    assert_eq!(
+        error.to_string(),
+        "invalid debug identifier \"DFB8E43AF2423D73A453AEB6A777EF75\": missing appendix at offset 32"
+    );
+
+    let error = DebugId::from_breakpad("DFB8E43AF2423D73A453AEB6A777EF75feedface1").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::TrailingGarbage);
+    assert_eq!(error.offset(), 40);
+}
+
+#[test]
+fn test_parse_error_input() {
+    let error = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-xyz").unwrap_err();
+    assert_eq!(
+        error.input(),
+        Some("dfb8e43a-f242-3d73-a453-aeb6a777ef75-xyz")
+    );
+    assert!(!error.is_input_truncated());
+
+    let long = format!("UUID: {}", "DFB8E43A-F242-3D73-A453-AEB6A777EF75".repeat(2));
+    let error = DebugId::from_str(&long).unwrap_err();
+    assert_eq!(error.input(), Some(&long[..48]));
+    assert!(error.is_input_truncated());
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "invalid debug identifier {:?}...: invalid hex digit at offset 0",
+            &long[..48]
+        )
+    );
+
+    // Truncation respects character boundaries.
+    let error = DebugId::from_str(&"아".repeat(20)).unwrap_err();
+    assert_eq!(error.input(), Some("아".repeat(16).as_str()));
+
+    assert_eq!(DebugId::from_str("").unwrap_err().input(), None);
+    assert_eq!(DebugId::from_codeview(b"RSDS").unwrap_err().input(), None);
+}
+
+#[test]
+fn test_parse_with() {
+    let hyphenated = "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a";
+    let compact = "dfb8e43af2423d73a453aeb6a777ef75a";
+    let expected = DebugId::from_str(hyphenated).unwrap();
+
+    assert_eq!(ParseOptions::default(), ParseOptions::new());
+    assert_eq!(
+        DebugId::parse_with(hyphenated, ParseOptions::new()),
+        Ok(expected)
+    );
+    assert_eq!(
+        DebugId::parse_with(compact, ParseOptions::breakpad()),
+        Ok(expected)
+    );
+
+    let options = ParseOptions::new().allow_hyphens(false);
+    assert_eq!(
+        DebugId::parse_with(hyphenated, options).unwrap_err().kind(),
+        ParseErrorKind::InvalidFormat
+    );
+    assert_eq!(DebugId::parse_with(compact, options), Ok(expected));
+
+    let options = ParseOptions::new().require_appendix(true);
+    assert!(DebugId::parse_with("dfb8e43af2423d73a453aeb6a777ef75", options).is_err());
+    assert!(DebugId::parse_with("5ccc38580", options).is_ok());
+
+    let options = ParseOptions::new().allow_tail(false);
+    assert!(DebugId::parse_with("dfb8e43af2423d73a453aeb6a777ef75feedface1", options).is_err());
+    assert!(DebugId::parse_with(
+        "dfb8e43af2423d73a453aeb6a777ef75feedface1",
+        ParseOptions::breakpad().allow_tail(true)
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_parse_strict() {
+    for valid in &[
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75",
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface",
+        "DFB8E43AF2423D73A453AEB6A777EF75a",
+        "5ccc38580",
+    ] {
+        assert_eq!(
+            DebugId::parse_strict(valid),
+            DebugId::from_str(valid),
+            "{}",
+            valid
+        );
+    }
+
+    let error =
+        DebugId::parse_strict("dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface1").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::TrailingGarbage);
+    assert_eq!(error.offset(), 45);
+
+    assert!(DebugId::parse_strict("5ccc3858-a").is_ok());
+
+    assert!(DebugId::parse_strict("dfb8e43af2423d73a453aeb6a777ef75-a").is_err());
+    assert!(DebugId::parse_strict("dfb8e43a-f242-3d73-a453-aeb6a777ef75a").is_err());
+}
+
+#[test]
+fn test_parse_braces() {
+    let expected = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    assert_eq!(
+        DebugId::from_str("{dfb8e43a-f242-3d73-a453-aeb6a777ef75}-a"),
+        Ok(expected)
+    );
+    assert_eq!(
+        DebugId::from_str("{DFB8E43A-F242-3D73-A453-AEB6A777EF75}a"),
+        Ok(expected)
+    );
+    assert_eq!(
+        DebugId::from_str("{dfb8e43a-f242-3d73-a453-aeb6a777ef75}"),
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75")
+    );
+
+    let error = DebugId::from_str("{dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidFormat);
+    let error = DebugId::from_str("{dfb8e43a-f242-3d73-a453-aeb6a7x7ef75}").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 31)
+    );
+    let error = DebugId::from_str("{dfb8e43a-f242-3d73-a453-aeb6a777ef75}ax").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 39)
+    );
+    let error = DebugId::from_str("{dfb8e43a-f242-3d73-a453-aeb6a777ef75}-").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::MissingAppendix, 39)
+    );
+
+    assert!(DebugId::parse_strict("{dfb8e43a-f242-3d73-a453-aeb6a777ef75}").is_err());
+    assert!(DebugId::from_breakpad("{dfb8e43a-f242-3d73-a453-aeb6a777ef75}a").is_err());
+}
+
+#[test]
+fn test_parse_urn() {
+    assert_eq!(
+        DebugId::from_str("urn:uuid:dfb8e43a-f242-3d73-a453-aeb6a777ef75"),
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75")
+    );
+    assert_eq!(
+        DebugId::from_str("URN:UUID:dfb8e43a-f242-3d73-a453-aeb6a777ef75-a"),
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a")
+    );
+
+    let error = DebugId::from_str("urn:uuid:dfb8e43a-f242-3d73-a453-aeb6a7x7ef75").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 39)
+    );
+    assert!(DebugId::from_str("urn:uuid:").is_err());
+    assert!(DebugId::from_str("urn:uuid:urn:uuid:dfb8e43a-f242-3d73-a453-aeb6a777ef75").is_err());
+    assert!(DebugId::parse_strict("urn:uuid:dfb8e43a-f242-3d73-a453-aeb6a777ef75").is_err());
+}
+
+#[test]
+fn test_parse_trim() {
+    let options = ParseOptions::new().trim(true);
+    let expected = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+
+    for input in &[
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a",
+        " dfb8e43a-f242-3d73-a453-aeb6a777ef75-a\n",
+        "UUID: dfb8e43a-f242-3d73-a453-aeb6a777ef75-a",
+        "Debug ID:\tdfb8e43a-f242-3d73-a453-aeb6a777ef75-a",
+        "dfb8e43a f242 3d73 a453 aeb6a777ef75 a",
+        "urn:uuid:dfb8e43a-f242-3d73-a453-aeb6a777ef75-a",
+    ] {
+        assert_eq!(
+            DebugId::parse_with(input, options),
+            Ok(expected),
+            "{}",
+            input
+        );
+    }
+
+    let error =
+        DebugId::parse_with("UUID:  dfb8e43a-f242-3d73-a453-aeb6 a7x7ef75", options).unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 38)
+    );
+
+    let error = DebugId::parse_with("UUID: ", options).unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::Empty);
+
+    assert!(DebugId::from_str(" dfb8e43a-f242-3d73-a453-aeb6a777ef75").is_err());
+}
+
+#[test]
+fn test_parse_pdb20_policy() {
+    let uuid = "dfb8e43a-f242-3d73-a453-aeb6a777ef75";
+    let pdb20 = "5ccc38580";
+
+    let options = ParseOptions::new().pdb20(Pdb20Policy::Forbid);
+    assert!(DebugId::parse_with(uuid, options).is_ok());
+    let error = DebugId::parse_with(pdb20, options).unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+
+    let options = ParseOptions::new().pdb20(Pdb20Policy::Require);
+    assert!(DebugId::parse_with(pdb20, options).unwrap().is_pdb20());
+    let error = DebugId::parse_with(uuid, options).unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+
+    let options = ParseOptions::new().pdb20(Pdb20Policy::Allow);
+    assert!(DebugId::parse_with(uuid, options).is_ok());
+    assert!(DebugId::parse_with(pdb20, options).is_ok());
+}
+
+#[test]
+fn test_parse_with_tail() {
+    let options = ParseOptions::new();
+    let expected = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface").unwrap();
+
+    let result = DebugId::parse_with_tail("dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface", options);
+    assert_eq!(result, Ok((expected, None)));
+
+    let result =
+        DebugId::parse_with_tail("dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface123", options);
+    assert_eq!(result, Ok((expected, Some("123"))));
+
+    let result = DebugId::parse_with_tail("DFB8E43AF2423D73A453AEB6A777EF75feedfacexyz", options);
+    assert_eq!(result, Ok((expected, Some("xyz"))));
+
+    let result = DebugId::parse_with_tail(
+        "{dfb8e43a-f242-3d73-a453-aeb6a777ef75}feedface123456",
+        options,
+    );
+    assert_eq!(result, Ok((expected, Some("123456"))));
+
+    let result = DebugId::parse_with_tail("5ccc3858a", options);
+    assert_eq!(result.unwrap().1, None);
+
+    let options = ParseOptions::new().trim(true);
+    let result = DebugId::parse_with_tail(
+        "UUID: dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface123 \n",
+        options,
+    );
+    assert_eq!(result, Ok((expected, Some("123"))));
+
+    let result =
+        DebugId::parse_with_tail("  dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface\n", options);
+    assert_eq!(result, Ok((expected, None)));
+}
+
+#[test]
+fn test_from_ascii() {
+    assert_eq!(
+        DebugId::from_ascii(b"dfb8e43a-f242-3d73-a453-aeb6a777ef75-a"),
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a")
+    );
+
+    let error = DebugId::from_ascii(b"dfb8e43a\xff").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::NonAscii, 8)
+    );
+    let error =
+        DebugId::from_ascii("dfb8e43a-f242-3d73-a453-aeb6a777ef75-\u{e4}".as_bytes()).unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::NonAscii, 37)
+    );
+}
+
+#[test]
+fn test_parse_any() {
+    let cases = [
+        (
+            "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a",
+            DebugIdFormat::Hyphenated,
+        ),
+        ("dfb8e43af2423d73a453aeb6a777ef75a", DebugIdFormat::Compact),
+        ("DFB8E43AF2423D73A453AEB6A777EF75", DebugIdFormat::Compact),
+        ("DFB8E43AF2423D73A453AEB6A777EF75a", DebugIdFormat::Breakpad),
+        ("418E89C31", DebugIdFormat::Pdb20),
+        ("418E89C3-1", DebugIdFormat::Pdb20),
+        (
+            "{dfb8e43a-f242-3d73-a453-aeb6a777ef75}",
+            DebugIdFormat::Braced,
+        ),
+        (
+            "urn:uuid:dfb8e43a-f242-3d73-a453-aeb6a777ef75",
+            DebugIdFormat::Urn,
+        ),
+    ];
+
+    for &(input, format) in &cases {
+        let (debug_id, parsed_format) = DebugId::parse_any(input).unwrap();
+        assert_eq!(Ok(debug_id), DebugId::from_str(input), "{}", input);
+        assert_eq!(parsed_format, format, "{}", input);
+    }
+
+    assert!(DebugId::parse_any("xyz").is_err());
+    assert_eq!(DebugIdFormat::Pdb20.to_string(), "PDB 2.0");
+}
+
+#[test]
+fn test_parse_many() {
+    let lines = [
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a",
+        "",
+        "418e89c31",
+        "dfb8e43a-f242-3d73",
+    ];
+
+    let results: Vec<_> = DebugId::parse_many(lines.iter().copied()).collect();
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0], DebugId::from_str(lines[0]));
+    assert_eq!(results[1].unwrap_err().kind(), ParseErrorKind::Empty);
+    assert!(results[2].as_ref().unwrap().is_pdb20());
+    assert_eq!(
+        results[3].unwrap_err().kind(),
+        ParseErrorKind::InvalidLength
+    );
+}
+
+#[test]
+fn test_is_valid() {
+    let inputs = [
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a",
+        "DFB8E43AF2423D73A453AEB6A777EF75a",
+        "418e89c3-1",
+        "{dfb8e43a-f242-3d73-a453-aeb6a777ef75}",
+        "",
+        "dfb8e43a-f242-3d73",
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef7g",
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-",
+    ];
+
+    for input in &inputs {
+        assert_eq!(
+            DebugId::is_valid(input),
+            DebugId::from_str(input).is_ok(),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_parse_symbol_path() {
+    let (debug_file, debug_id) =
+        DebugId::parse_symbol_path("xul.pdb/DFB8E43AF2423D73A453AEB6A777EF75a/xul.sym").unwrap();
+    assert_eq!(debug_file, "xul.pdb");
+    assert_eq!(
+        debug_id,
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap()
+    );
+
+    let (debug_file, debug_id) =
+        DebugId::parse_symbol_path("C:\\symbols\\wntdll.pdb\\418E89C31\\wntdll.pd_").unwrap();
+    assert_eq!(debug_file, "wntdll.pdb");
+    assert!(debug_id.is_pdb20());
+
+    let (debug_file, _) = DebugId::parse_symbol_path(
+        "https://symbols.example.com/libxul.so/DFB8E43AF2423D73A453AEB6A777EF750/libxul.so.sym",
+    )
+    .unwrap();
+    assert_eq!(debug_file, "libxul.so");
+
+    let error = DebugId::parse_symbol_path("xul.pdb/xul.sym").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidFormat);
+
+    let error = DebugId::parse_symbol_path("").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::Empty);
+
+    let error =
+        DebugId::parse_symbol_path("a.pdb/DFB8E43AF2423D73A453AEB6A777EF7Xa/a.sym").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidHex);
+    assert_eq!(error.offset(), 37);
+    assert_eq!(
+        error.input(),
+        Some("a.pdb/DFB8E43AF2423D73A453AEB6A777EF7Xa/a.sym")
+    );
+}
+
+#[test]
+fn test_parse_uuid_line() {
+    let expected = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+
+    let (debug_id, arch) = DebugId::parse_uuid_line(
+        "UUID: DFB8E43A-F242-3D73-A453-AEB6A777EF75 (arm64) /usr/lib/libfoo.dylib",
+    )
+    .unwrap();
+    assert_eq!(debug_id, expected);
+    assert_eq!(arch, Some("arm64"));
+
+    let (debug_id, arch) =
+        DebugId::parse_uuid_line("      uuid DFB8E43A-F242-3D73-A453-AEB6A777EF75").unwrap();
+    assert_eq!(debug_id, expected);
+    assert_eq!(arch, None);
+
+    let (debug_id, arch) =
+        DebugId::parse_uuid_line("dfb8e43a-f242-3d73-a453-aeb6a777ef75 (x86_64").unwrap();
+    assert_eq!(debug_id, expected);
+    assert_eq!(arch, None);
+
+    let error = DebugId::parse_uuid_line("UUID:").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::Empty);
+
+    let error =
+        DebugId::parse_uuid_line("UUID: DFB8E43A-F242-3D73-A453-AEB6A777EF7X (arm64)").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidHex);
+    assert_eq!(error.offset(), 41);
+}
+
+#[test]
+fn test_to_inline_string() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface").unwrap();
+
+    let styles = [
+        DebugIdStyle::Hyphenated,
+        DebugIdStyle::Compact,
+        DebugIdStyle::Breakpad,
+        DebugIdStyle::Symstore,
+    ];
+
+    for &style in &styles {
+        let mut expected = String::new();
+        id.write_to(&mut expected, style).unwrap();
+        assert_eq!(id.to_inline_string(style), expected.as_str());
+    }
+
+    let string = id.to_inline_string(DebugIdStyle::Hyphenated);
+    assert_eq!(string.len(), 45);
+    assert_eq!(
+        format!("{:?}", string),
+        "\"dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface\""
+    );
+    assert_eq!(String::from(string), id.to_string());
+}
+
+#[test]
+fn test_simple() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-feedface").unwrap();
+    assert_eq!(
+        id.simple().to_string(),
+        "dfb8e43af2423d73a453aeb6a777ef75feedface"
+    );
+    assert_eq!(id.simple().to_string(), format!("{:x}", id));
+
+    let id = DebugId::from_str("418e89c3-1").unwrap();
+    assert_eq!(id.simple().to_string(), "418e89c31");
+    assert_eq!(format!("{:>12}", id.simple()), "   418e89c31");
+}
+
+#[test]
+fn test_parse_appendix_separators() {
+    let options = ParseOptions::new().allow_appendix_separators(true);
+    let expected = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+
+    let inputs = [
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75+a",
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75.a",
+        "dfb8e43af2423d73a453aeb6a777ef75+a",
+        "{dfb8e43a-f242-3d73-a453-aeb6a777ef75}.a",
+    ];
+
+    for input in &inputs {
+        let id = DebugId::parse_with(input, options).unwrap();
+        assert_eq!(id, expected, "{}", input);
+        assert!(DebugId::from_str(input).is_err(), "{}", input);
+    }
+
+    let id = DebugId::parse_with("418e89c3+1", options).unwrap();
+    assert_eq!(id.to_string(), "418E89C3-1");
+
+    let error = DebugId::parse_with("dfb8e43a-f242-3d73-a453-aeb6a777ef75+", options).unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::MissingAppendix);
+    assert_eq!(error.offset(), 37);
+
+    let error =
+        DebugId::parse_with("dfb8e43a-f242-3d73-a453-aeb6a777ef75+-a", options).unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidHex);
+}
+
+#[test]
+fn test_parse_non_ascii() {
+    let error = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-\u{e4}").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::NonAscii);
+    assert_eq!(error.offset(), 37);
+    assert_eq!(
+        error.to_string(),
+        "invalid debug identifier \"dfb8e43a-f242-3d73-a453-aeb6a777ef75-\u{e4}\": \
+         non-ASCII character at offset 37"
+    );
+
+    let error = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-g").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidHex);
+
+    let code_id = CodeId::new_preserving("dfb8\u{e4}".into());
+    let error = DebugId::try_from_code_id(&code_id, FileFormat::Elf).unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::NonAscii);
+    assert_eq!(error.offset(), 4);
+}
+
+#[test]
+fn test_minidump_module_ids() {
+    let expected = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-1").unwrap();
+
+    let (debug_id, code_id) = minidump::parse_module_ids(
+        "libfoo.so",
+        "DFB8E43AF2423D73A453AEB6A777EF751",
+        "3ae4b8df42f2733da453aeb6a777ef7593a66d4f",
+    )
+    .unwrap();
+    assert_eq!(debug_id, Some(expected));
+    assert_eq!(
+        code_id,
+        Some(CodeId::new(
+            "3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into()
+        ))
+    );
+
+    let ids = minidump::parse_module_ids("", "DFB8E43AF2423D73A453AEB6A777EF751", "").unwrap();
+    assert_eq!(ids, (None, None));
+
+    let ids =
+        minidump::parse_module_ids("libfoo.so", "000000000000000000000000000000000", "00000000")
+            .unwrap();
+    assert_eq!(ids, (None, None));
+
+    let ids = minidump::parse_module_ids("libfoo.so", " ", "id").unwrap();
+    assert_eq!(ids, (None, None));
+
+    let error = minidump::parse_module_ids("libfoo.so", "DFB8E43A-F242-3D73", "").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+}
+
+#[test]
+fn test_parse_appendix_mode() {
+    let input = "dfb8e43a-f242-3d73-a453-aeb6a777ef75-123456789abcdef0";
+
+    let options = ParseOptions::new().appendix(AppendixMode::Strict);
+    let error = DebugId::parse_with(input, options).unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::TrailingGarbage, 45)
+    );
+
+    let options = ParseOptions::new().appendix(AppendixMode::Truncate);
+    let (id, tail) = DebugId::parse_with_tail(input, options).unwrap();
+    assert_eq!(id.appendix(), 0x1234_5678);
+    assert_eq!(tail, Some("9abcdef0"));
+
+    let options = ParseOptions::new().appendix(AppendixMode::Wide);
+    let (id, tail) = DebugId::parse_with_tail(input, options).unwrap();
+    assert_eq!(id.appendix(), 0x9abc_def0);
+    assert_eq!(tail, None);
+    assert_eq!(
+        DebugId::parse_with("dfb8e43af2423d73a453aeb6a777ef75a", options)
+            .unwrap()
+            .appendix(),
+        10
+    );
+
+    let error = DebugId::parse_with(&format!("{}1", input), options).unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::TrailingGarbage, 53)
+    );
+    let error = DebugId::parse_with(
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-1x3456789abcdef0",
+        options,
+    )
+    .unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 38)
+    );
+
+    let options = options.trim(true);
+    let id = DebugId::parse_with(&format!("  {}  ", input), options).unwrap();
+    assert_eq!(id.appendix(), 0x9abc_def0);
+
+    let braced = "{dfb8e43a-f242-3d73-a453-aeb6a777ef75}123456789abcdef0";
+    let id = DebugId::parse_with(braced, options).unwrap();
+    assert_eq!(id.appendix(), 0x9abc_def0);
+}
+
+#[test]
+fn test_portable_pdb() {
+    let uuid = Uuid::parse_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+    let id = DebugId::portable_pdb(uuid);
+    assert_eq!(id.uuid(), uuid);
+    assert_eq!(id.appendix(), 0xffff_ffff);
+    assert!(id.is_portable_pdb());
+    assert_eq!(id.bump_appendix(), None);
+    assert_eq!(
+        id.to_string(),
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-ffffffff"
+    );
+
+    assert!(!DebugId::from_uuid(uuid).is_portable_pdb());
+    assert!(!DebugId::from_pdb20(0x5ab3_80ba, 0xffff_ffff).is_portable_pdb());
+}
+
+#[test]
+fn test_shard_prefix() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    assert_eq!(id.shard_prefix(0), "");
+    assert_eq!(id.shard_prefix(2), "df");
+    assert_eq!(id.shard_prefix(12), "dfb8e43af242");
+    assert_eq!(id.shard_prefix(100), "dfb8e43af2423d73a453aeb6a777ef75a");
+
+    let id = DebugId::from_str("DFB8E43A-F242-3D73-A453-AEB6A777EF75").unwrap();
+    assert_eq!(id.shard_prefix(33), "dfb8e43af2423d73a453aeb6a777ef750");
+}
+
+#[test]
+fn test_match_module() {
+    let wanted = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-1").unwrap();
+    let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+
+    assert_eq!(
+        match_module(&wanted, Some(&wanted), None),
+        MatchQuality::Exact
+    );
+    assert_eq!(
+        match_module(&wanted, Some(&wanted.with_appendix(2)), None),
+        MatchQuality::AgeMismatch
+    );
+    assert_eq!(match_module(&wanted, None, None), MatchQuality::None);
+    assert_eq!(
+        match_module(&wanted, Some(&DebugId::nil()), None),
+        MatchQuality::None
+    );
+
+    // Debug identifiers with an appendix are never derived from code identifiers.
+    assert_eq!(
+        match_module(&wanted, None, Some(&code_id)),
+        MatchQuality::None
+    );
+
+    let wanted = wanted.zero_appendix();
+    assert_eq!(
+        match_module(&wanted, None, Some(&code_id)),
+        MatchQuality::CodeId
+    );
+    let other = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5").unwrap();
+    assert_eq!(
+        match_module(&wanted, Some(&other), Some(&code_id)),
+        MatchQuality::CodeId
+    );
+    assert_eq!(
+        match_module(&wanted, Some(&other), None),
+        MatchQuality::None
+    );
+
+    let nil = DebugId::nil();
+    assert_eq!(match_module(&nil, Some(&nil), None), MatchQuality::None);
+
+    let pdb20 = DebugId::from_pdb20(0x5ab3_80ba, 1);
+    assert_eq!(
+        match_module(&pdb20, Some(&DebugId::from_pdb20(0x5ab3_80ba, 3)), None),
+        MatchQuality::AgeMismatch
+    );
+    assert_eq!(
+        match_module(&pdb20, Some(&DebugId::from_pdb20(0x5ab3_80bb, 1)), None),
+        MatchQuality::None
+    );
+}
+
+#[test]
+fn test_match_quality_order() {
+    let mut qualities = vec![
+        MatchQuality::CodeId,
+        MatchQuality::None,
+        MatchQuality::Exact,
+        MatchQuality::AgeMismatch,
+    ];
+    qualities.sort();
+    assert_eq!(
+        qualities,
+        vec![
+            MatchQuality::None,
+            MatchQuality::AgeMismatch,
+            MatchQuality::CodeId,
+            MatchQuality::Exact,
+        ]
+    );
+}
+
 #[test]
 fn test_from_guid_age() {
     let guid = [
@@ -136,6 +868,18 @@ fn test_from_guid_age() {
     )
 }
 
+#[test]
+fn test_guid_age() {
+    let guid = [
+        0x98, 0xd1, 0xef, 0xe8, 0x6e, 0xf8, 0xfe, 0x45, 0x9d, 0xdb, 0xe1, 0x13, 0x82, 0xb5, 0xd1,
+        0xc9,
+    ];
+
+    let debug_id = DebugId::from_guid_age(&guid[..], 1).unwrap();
+    assert_eq!(debug_id.guid(), guid);
+    assert_eq!(debug_id.age(), 1);
+}
+
 #[test]
 fn test_parse_breakpad_zero() {
     assert_eq!(
@@ -169,6 +913,20 @@ fn test_parse_breakpad_long() {
     );
 }
 
+#[test]
+fn test_parse_breakpad_lowercase() {
+    let debug_id = DebugId::from_breakpad("dfb8e43af2423d73a453aeb6a777ef75a").unwrap();
+
+    assert_eq!(
+        debug_id,
+        DebugId::from_breakpad("DFB8E43AF2423D73A453AEB6A777EF75A").unwrap()
+    );
+    assert_eq!(
+        debug_id.breakpad().to_string(),
+        "DFB8E43AF2423D73A453AEB6A777EF75a"
+    );
+}
+
 #[test]
 fn test_parse_breakpad_error_tail() {
     assert!(DebugId::from_breakpad("DFB8E43AF2423D73A453AEB6A777EF75feedface123").is_err());
@@ -242,7 +1000,7 @@ fn test_debug_id_debug() {
 }
 
 #[test]
-#[cfg(feature = "with_serde")]
+#[cfg(feature = "serde")]
 fn test_serde_serialize() {
     let id = DebugId::from_parts(
         Uuid::parse_str("DFB8E43AF2423D73A453AEB6A777EF75").unwrap(),
@@ -256,7 +1014,7 @@ fn test_serde_serialize() {
 }
 
 #[test]
-#[cfg(feature = "with_serde")]
+#[cfg(feature = "serde")]
 fn test_serde_deserialize() {
     let id: DebugId = serde_json::from_str("\"dfb8e43a-f242-3d73-a453-aeb6a777ef75-a\"")
         .expect("could not deserialize");
@@ -283,6 +1041,30 @@ fn test_pdb20() {
     );
 }
 
+#[test]
+fn test_uuid_opt() {
+    let debug_id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    assert_eq!(debug_id.uuid_opt(), Some(debug_id.uuid()));
+
+    let debug_id = DebugId::from_pdb20(0x418e_89c3, 1);
+    assert_eq!(debug_id.uuid_opt(), None);
+}
+
+#[test]
+fn test_uuid_version_variant() {
+    let debug_id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    assert_eq!(debug_id.uuid_version(), Some(3));
+    assert_eq!(debug_id.uuid_variant(), Some(uuid::Variant::RFC4122));
+
+    let debug_id = DebugId::from_str("dfb8e43a-f242-0d73-0453-aeb6a777ef75").unwrap();
+    assert_eq!(debug_id.uuid_version(), Some(0));
+    assert_eq!(debug_id.uuid_variant(), Some(uuid::Variant::NCS));
+
+    let debug_id = DebugId::from_pdb20(0x418e_89c3, 1);
+    assert_eq!(debug_id.uuid_version(), None);
+    assert_eq!(debug_id.uuid_variant(), None);
+}
+
 #[test]
 fn test_pdb20_format() {
     let timestamp: u32 = 0x418e89c3;
@@ -293,6 +1075,15 @@ fn test_pdb20_format() {
     assert_eq!(debug_id.breakpad().to_string(), "418E89C31");
 }
 
+#[test]
+fn test_pdb20_format_zero_age() {
+    let debug_id = DebugId::from_pdb20(0x418e_89c3, 0);
+
+    assert_eq!(debug_id.to_string(), "418E89C3-0");
+    assert_eq!(debug_id.breakpad().to_string(), "418E89C30");
+    assert_eq!(DebugId::from_str(&debug_id.to_string()), Ok(debug_id));
+}
+
 #[test]
 fn test_pdb20_parse() {
     let timestamp: u32 = 0x418e89c3;
@@ -386,3 +1177,461 @@ fn test_default() {
     assert_eq!(debug_id.uuid(), Uuid::nil());
     assert_eq!(debug_id.appendix(), 0);
 }
+
+#[test]
+fn test_from_codeview_pdb70() {
+    let mut record = b"RSDS".to_vec();
+    record.extend_from_slice(&[
+        0x98, 0xd1, 0xef, 0xe8, 0x6e, 0xf8, 0xfe, 0x45, 0x9d, 0xdb, 0xe1, 0x13, 0x82, 0xb5, 0xd1,
+        0xc9,
+    ]);
+    record.extend_from_slice(&1u32.to_le_bytes());
+    record.extend_from_slice(b"C:\\projects\\foo.pdb\0");
+
+    assert_eq!(
+        DebugId::from_codeview(&record).unwrap(),
+        DebugId::from_str("e8efd198-f86e-45fe-9ddb-e11382b5d1c9-1").unwrap()
+    );
+}
+
+#[test]
+fn test_from_codeview_pdb20() {
+    let mut record = b"NB10".to_vec();
+    record.extend_from_slice(&0u32.to_le_bytes());
+    record.extend_from_slice(&0x418e_89c3u32.to_le_bytes());
+    record.extend_from_slice(&1u32.to_le_bytes());
+    record.extend_from_slice(b"foo.pdb\0");
+
+    assert_eq!(
+        DebugId::from_codeview(&record).unwrap(),
+        DebugId::from_pdb20(0x418e_89c3, 1)
+    );
+}
+
+#[test]
+fn test_from_codeview_error() {
+    assert!(DebugId::from_codeview(b"RSDS\x98\xd1\xef\xe8").is_err());
+    assert!(DebugId::from_codeview(b"XXXX").is_err());
+    assert!(DebugId::from_codeview(b"").is_err());
+}
+
+#[test]
+fn test_to_codeview_roundtrip() {
+    let debug_id = DebugId::from_str("e8efd198-f86e-45fe-9ddb-e11382b5d1c9-1").unwrap();
+    let record = debug_id.to_codeview("foo.pdb");
+
+    assert_eq!(&record[..4], b"RSDS");
+    assert_eq!(&record[4..8], &[0x98, 0xd1, 0xef, 0xe8]);
+    assert_eq!(&record[24..], b"foo.pdb\0");
+    assert_eq!(DebugId::from_codeview(&record).unwrap(), debug_id);
+}
+
+#[test]
+fn test_to_codeview_pdb20() {
+    let debug_id = DebugId::from_pdb20(0x418e_89c3, 1);
+    let record = debug_id.to_codeview("foo.pdb");
+
+    assert_eq!(&record[..4], b"NB10");
+    assert_eq!(&record[16..], b"foo.pdb\0");
+    assert_eq!(DebugId::from_codeview(&record).unwrap(), debug_id);
+}
+
+#[test]
+fn test_from_data() {
+    let debug_id = DebugId::from_data(Uuid::NAMESPACE_URL, b"https://example.com/app.min.js");
+
+    assert_eq!(debug_id.appendix(), 0);
+    assert_eq!(debug_id.uuid().get_version_num(), 5);
+    assert_eq!(
+        debug_id,
+        DebugId::from_data(Uuid::NAMESPACE_URL, b"https://example.com/app.min.js")
+    );
+    assert_ne!(
+        debug_id,
+        DebugId::from_data(Uuid::NAMESPACE_OID, b"https://example.com/app.min.js")
+    );
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_random() {
+    let debug_id = DebugId::random();
+
+    assert_eq!(debug_id.appendix(), 0);
+    assert_eq!(debug_id.uuid().get_version_num(), 4);
+    assert_ne!(debug_id, DebugId::random());
+}
+
+#[test]
+fn test_with_appendix() {
+    let debug_id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+
+    assert_eq!(
+        debug_id.with_appendix(0xb).to_string(),
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-b"
+    );
+    assert_eq!(
+        debug_id.zero_appendix().to_string(),
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75"
+    );
+
+    let pdb20 = DebugId::from_pdb20(0x418e_89c3, 1).with_appendix(2);
+    assert!(pdb20.is_pdb20());
+    assert_eq!(pdb20, DebugId::from_pdb20(0x418e_89c3, 2));
+}
+
+#[test]
+fn test_try_into_parts() {
+    let debug_id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    let (uuid, appendix) = <(Uuid, u32)>::try_from(debug_id).unwrap();
+
+    assert_eq!(
+        uuid,
+        Uuid::parse_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap()
+    );
+    assert_eq!(appendix, 0xa);
+}
+
+#[test]
+fn test_try_into_parts_pdb20() {
+    let debug_id = DebugId::from_pdb20(0x418e_89c3, 1);
+    assert!(<(Uuid, u32)>::try_from(debug_id).is_err());
+}
+
+#[test]
+fn test_from_bytes() {
+    let bytes = [
+        0xdf, 0xb8, 0xe4, 0x3a, 0xf2, 0x42, 0x3d, 0x73, 0xa4, 0x53, 0xae, 0xb6, 0xa7, 0x77, 0xef,
+        0x75,
+    ];
+
+    assert_eq!(
+        DebugId::from(bytes),
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap()
+    );
+}
+
+#[test]
+fn test_matches_code_id_mach() {
+    let debug_id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+    let code_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    assert!(debug_id.matches_code_id(&code_id));
+}
+
+#[test]
+fn test_matches_code_id_elf() {
+    let debug_id = DebugId::from_str("3ae4b8df-42f2-733d-a453-aeb6a777ef75").unwrap();
+    let code_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75feedface".into());
+    assert!(debug_id.matches_code_id(&code_id));
+
+    // Short build ids are padded with zeros.
+    let debug_id = DebugId::from_str("3ae4b8df-42f2-733d-0000-000000000000").unwrap();
+    let code_id = CodeId::new("dfb8e43af2423d73".into());
+    assert!(debug_id.matches_code_id(&code_id));
+}
+
+#[test]
+fn test_matches_code_id_mismatch() {
+    let debug_id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-1").unwrap();
+    let code_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    assert!(!debug_id.matches_code_id(&code_id));
+
+    let debug_id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+    let code_id = CodeId::new("5ccc38584b08000".into());
+    assert!(!debug_id.matches_code_id(&code_id));
+    assert!(!debug_id.matches_code_id(&CodeId::nil()));
+}
+
+#[test]
+fn test_to_string_padded() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+
+    assert_eq!(
+        format!("{:>40}|", id),
+        "  dfb8e43a-f242-3d73-a453-aeb6a777ef75-a|"
+    );
+    assert_eq!(
+        format!("{:-<40}|", id),
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a--|"
+    );
+    assert_eq!(
+        format!("{:^14}|", DebugId::from_pdb20(0x418e_89c3, 1)),
+        "  418E89C3-1  |"
+    );
+    assert_eq!(
+        format!("{:>36}|", id.breakpad()),
+        "   DFB8E43AF2423D73A453AEB6A777EF75a|"
+    );
+}
+
+#[test]
+fn test_to_string_alternate() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+
+    assert_eq!(format!("{:#}", id), "DFB8E43AF2423D73A453AEB6A777EF75a");
+    assert_eq!(
+        format!("{:>#35}", id),
+        "  DFB8E43AF2423D73A453AEB6A777EF75a"
+    );
+    assert_eq!(format!("{:#x}", id), "dfb8e43af2423d73a453aeb6a777ef75a");
+    assert_eq!(format!("{:#X}", id), "DFB8E43AF2423D73A453AEB6A777EF75A");
+}
+
+#[test]
+fn test_to_string_hex() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    assert_eq!(format!("{:x}", id), "dfb8e43af2423d73a453aeb6a777ef75a");
+    assert_eq!(format!("{:X}", id), "DFB8E43AF2423D73A453AEB6A777EF75A");
+    assert_eq!(
+        format!("{:>35x}", id),
+        "  dfb8e43af2423d73a453aeb6a777ef75a"
+    );
+
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+    assert_eq!(format!("{:x}", id), "dfb8e43af2423d73a453aeb6a777ef750");
+
+    let id = DebugId::from_pdb20(0x418e_89c3, 0xa);
+    assert_eq!(format!("{:x}", id), "418e89c3a");
+    assert_eq!(format!("{:X}", id), "418E89C3A");
+}
+
+#[test]
+fn test_to_string_breakpad_case() {
+    let id = DebugId::from_parts(
+        Uuid::parse_str("DFB8E43AF2423D73A453AEB6A777EF75").unwrap(),
+        0xa,
+    );
+
+    assert_eq!(
+        id.breakpad().lowercase().to_string(),
+        "dfb8e43af2423d73a453aeb6a777ef75a"
+    );
+    assert_eq!(
+        id.breakpad().uppercase().to_string(),
+        "DFB8E43AF2423D73A453AEB6A777EF75A"
+    );
+}
+
+#[test]
+fn test_ordering() {
+    let mut ids = vec![
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-1").unwrap(),
+        DebugId::from_pdb20(0x418e_89c3, 1),
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap(),
+        DebugId::from_parts(
+            Uuid::parse_str("418e89c3-0000-0000-0000-000000000000").unwrap(),
+            1,
+        ),
+        DebugId::nil(),
+        DebugId::from_str("00000000-0000-0000-0000-000000000001").unwrap(),
+    ];
+    ids.sort();
+
+    assert_eq!(
+        ids,
+        vec![
+            DebugId::nil(),
+            DebugId::from_str("00000000-0000-0000-0000-000000000001").unwrap(),
+            DebugId::from_parts(
+                Uuid::parse_str("418e89c3-0000-0000-0000-000000000000").unwrap(),
+                1,
+            ),
+            DebugId::from_pdb20(0x418e_89c3, 1),
+            DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap(),
+            DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-1").unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn test_non_nil() {
+    let debug_id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    let non_nil = NonNilDebugId::new(debug_id).unwrap();
+    assert_eq!(non_nil.get(), debug_id);
+    assert_eq!(non_nil.to_string(), debug_id.to_string());
+
+    let pdb20 = DebugId::from_pdb20(0x418e_89c3, 1);
+    assert_eq!(NonNilDebugId::new(pdb20).unwrap().get(), pdb20);
+
+    assert!(NonNilDebugId::new(DebugId::nil()).is_none());
+    assert!(NonNilDebugId::from_str("00000000-0000-0000-0000-000000000000").is_err());
+}
+
+#[test]
+fn test_non_nil_mem() {
+    assert_eq!(
+        size_of::<Option<NonNilDebugId>>(),
+        size_of::<NonNilDebugId>()
+    );
+}
+
+#[test]
+fn test_u128() {
+    let debug_id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    let value = debug_id.as_u128();
+
+    assert_eq!(value, 0xdfb8_e43a_f242_3d73_a453_aeb6_a777_ef75);
+    assert_eq!(DebugId::from_u128(value), debug_id.zero_appendix());
+
+    let pdb20 = DebugId::from_pdb20(0x418e_89c3, 1);
+    assert_eq!(pdb20.as_u128(), 0x418e_89c3 << 96);
+    assert!(!DebugId::from_u128(pdb20.as_u128()).is_pdb20());
+}
+
+#[test]
+fn test_write_to() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+
+    let mut string = String::new();
+    id.write_to(&mut string, DebugIdStyle::Hyphenated).unwrap();
+    assert_eq!(string, "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a");
+
+    let mut string = String::new();
+    id.write_to(&mut string, DebugIdStyle::Compact).unwrap();
+    assert_eq!(string, "dfb8e43af2423d73a453aeb6a777ef75a");
+
+    let mut string = String::new();
+    id.write_to(&mut string, DebugIdStyle::Breakpad).unwrap();
+    assert_eq!(string, "DFB8E43AF2423D73A453AEB6A777EF75a");
+}
+
+#[test]
+fn test_stable_hash() {
+    // These values must never change.
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    assert_eq!(id.stable_hash(), 0x48c1_41b0_168f_72f3);
+    assert_eq!(DebugId::nil().stable_hash(), 0x98b2_b141_8e80_a50f);
+
+    let pdb20 = DebugId::from_pdb20(0x418e_89c3, 1);
+    let uuid = DebugId::from_parts(pdb20.uuid(), 1);
+    assert_ne!(pdb20.stable_hash(), uuid.stable_hash());
+}
+
+#[test]
+fn test_try_from_code_id_elf() {
+    let code_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75feedface".into());
+    assert_eq!(
+        DebugId::try_from_code_id(&code_id, FileFormat::Elf).unwrap(),
+        DebugId::from_str("3ae4b8df-42f2-733d-a453-aeb6a777ef75").unwrap()
+    );
+
+    let code_id = CodeId::new("dfb8e43af2423d73".into());
+    assert_eq!(
+        DebugId::try_from_code_id(&code_id, FileFormat::Elf).unwrap(),
+        DebugId::from_str("3ae4b8df-42f2-733d-0000-000000000000").unwrap()
+    );
+}
+
+#[test]
+fn test_try_from_code_id_mach() {
+    let code_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    assert_eq!(
+        DebugId::try_from_code_id(&code_id, FileFormat::MachO).unwrap(),
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap()
+    );
+
+    let code_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75feedface".into());
+    assert!(DebugId::try_from_code_id(&code_id, FileFormat::MachO).is_err());
+}
+
+#[test]
+fn test_try_from_code_id_wasm() {
+    let code_id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75feedface".into());
+    assert_eq!(
+        DebugId::try_from_code_id(&code_id, FileFormat::Wasm).unwrap(),
+        DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap()
+    );
+}
+
+#[test]
+fn test_try_from_code_id_error() {
+    let code_id = CodeId::new("5ccc38584b08000".into());
+    assert!(DebugId::try_from_code_id(&code_id, FileFormat::Pe).is_err());
+    assert!(DebugId::try_from_code_id(&code_id, FileFormat::Elf).is_err());
+    assert!(DebugId::try_from_code_id(&CodeId::nil(), FileFormat::Elf).is_err());
+}
+
+#[test]
+fn test_is_valid_for() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+    assert!(id.is_valid_for(FileFormat::Elf));
+    assert!(id.is_valid_for(FileFormat::MachO));
+    assert!(id.is_valid_for(FileFormat::Pe));
+
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-1").unwrap();
+    assert!(!id.is_valid_for(FileFormat::Elf));
+    assert!(!id.is_valid_for(FileFormat::MachO));
+    assert!(id.is_valid_for(FileFormat::Pe));
+
+    let id = DebugId::from_pdb20(0x418e_89c3, 0);
+    assert!(!id.is_valid_for(FileFormat::Elf));
+    assert!(id.is_valid_for(FileFormat::Pe));
+
+    assert!(!DebugId::nil().is_valid_for(FileFormat::Pe));
+}
+
+#[test]
+fn test_from_namespace() {
+    let id = DebugId::from_namespace(namespace::URL, b"https://example.com/app.min.js");
+
+    assert_eq!(id.to_string(), "29827a20-1539-5fcf-9d11-eab406b0bafc");
+    assert_eq!(
+        id,
+        DebugId::from_data(namespace::URL, b"https://example.com/app.min.js")
+    );
+}
+
+#[test]
+fn test_bump_appendix() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    assert_eq!(id.bump_appendix(), Some(id.with_appendix(0xb)));
+
+    let id = DebugId::from_pdb20(0x418e_89c3, 1);
+    assert_eq!(
+        id.bump_appendix(),
+        Some(DebugId::from_pdb20(0x418e_89c3, 2))
+    );
+
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+    assert_eq!(id.bump_appendix(), None);
+    assert_eq!(id.with_appendix(u32::MAX).bump_appendix(), None);
+}
+
+#[test]
+fn test_representations() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+    let representations = id.representations();
+
+    assert_eq!(
+        representations.hyphenated,
+        "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a"
+    );
+    assert_eq!(representations.compact, "dfb8e43af2423d73a453aeb6a777ef75a");
+    assert_eq!(
+        representations.breakpad,
+        "DFB8E43AF2423D73A453AEB6A777EF75a"
+    );
+    assert_eq!(
+        representations.symstore,
+        "DFB8E43AF2423D73A453AEB6A777EF75A"
+    );
+    assert_eq!(
+        representations.to_string(),
+        "hyphenated: dfb8e43a-f242-3d73-a453-aeb6a777ef75-a\n\
+         compact:    dfb8e43af2423d73a453aeb6a777ef75a\n\
+         breakpad:   DFB8E43AF2423D73A453AEB6A777EF75a\n\
+         symstore:   DFB8E43AF2423D73A453AEB6A777EF75A\n"
+    );
+}
+
+#[test]
+fn test_eq_str() {
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a").unwrap();
+
+    assert_eq!(id, "dfb8e43a-f242-3d73-a453-aeb6a777ef75-a");
+    assert_eq!(id, "DFB8E43AF2423D73A453AEB6A777EF75a");
+    assert_eq!("dfb8e43a-f242-3d73-a453-aeb6a777ef75-a", id);
+    assert_eq!(*"dfb8e43a-f242-3d73-a453-aeb6a777ef75-a", id);
+    assert_ne!(id, "dfb8e43a-f242-3d73-a453-aeb6a777ef75");
+    assert_ne!(id, "invalid");
+}
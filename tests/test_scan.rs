@@ -0,0 +1,96 @@
+#![cfg(feature = "scan")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use debugid::scan::scan;
+use debugid::symkey::{DebuginfodKind, KeyId, SymbolKey};
+use debugid::{CodeId, DebugId};
+
+/// Creates an empty directory for a test in the temporary directory.
+fn temp_store(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("debugid-{}-{}", name, std::process::id()));
+    fs::remove_dir_all(&root).ok();
+    fs::create_dir_all(&root).unwrap();
+    root
+}
+
+fn create_file(root: &Path, key: &str) {
+    let path = root.join(key);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, b"").unwrap();
+}
+
+#[test]
+fn test_scan() {
+    let root = temp_store("scan");
+    let debug_id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    let build_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+
+    create_file(&root, &debug_id.symstore_key("wkernel32.pdb"));
+    create_file(&root, &format!("nested/{}", debug_id.ssqp_key("xul.pdb")));
+    create_file(&root, "3a/e4b8df42f2733da453aeb6a777ef7593a66d4f/debuginfo");
+    create_file(&root, "000Admin/history.txt");
+    create_file(&root, "index2.txt");
+
+    let mut entries = scan(&root).collect::<Result<Vec<_>, _>>().unwrap();
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let paths = entries
+        .iter()
+        .map(|entry| entry.path.strip_prefix(&root).unwrap().to_path_buf())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            PathBuf::from("3a/e4b8df42f2733da453aeb6a777ef7593a66d4f/debuginfo"),
+            PathBuf::from("nested/xul.pdb/ff9f9f7841db88f0cdeda9e1e9bff3b51/xul.pdb"),
+            PathBuf::from("wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pdb"),
+        ]
+    );
+
+    assert_eq!(entries[0].id(), KeyId::Code(build_id));
+    assert_eq!(entries[1].key, SymbolKey::ssqp_debug("xul.pdb", debug_id));
+    assert_eq!(
+        entries[2].key,
+        SymbolKey::symstore("wkernel32.pdb", debug_id)
+    );
+    assert_eq!(entries[2].id(), KeyId::Debug(debug_id));
+
+    fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn test_scan_missing_root() {
+    let root = temp_store("scan-missing");
+    let mut scan = scan(root.join("missing"));
+    assert!(scan.next().unwrap().is_err());
+    assert!(scan.next().is_none());
+
+    fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn test_scan_debuginfod_section() {
+    let root = temp_store("scan-section");
+    let build_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+
+    create_file(&root, &build_id.debuginfod_section_path(".gdb_index"));
+    create_file(&root, &build_id.debuginfod_path(DebuginfodKind::Debuginfo));
+
+    let mut entries = scan(&root).collect::<Result<Vec<_>, _>>().unwrap();
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let keys = entries.iter().map(|entry| &entry.key).collect::<Vec<_>>();
+    assert_eq!(
+        keys,
+        vec![
+            &SymbolKey::debuginfod(DebuginfodKind::Debuginfo, build_id.clone()),
+            &SymbolKey::debuginfod_section(build_id.clone(), ".gdb_index"),
+        ]
+    );
+    assert_eq!(entries[1].id(), KeyId::Code(build_id));
+
+    fs::remove_dir_all(&root).ok();
+}
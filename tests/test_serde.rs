@@ -1,6 +1,6 @@
 #![cfg(feature = "serde")]
 
-use debugid::{CodeId, DebugId};
+use debugid::{CodeId, CodeIdArc, CodeIdKind, DebugId, FatCodeId, NonNilDebugId};
 use uuid::Uuid;
 
 #[test]
@@ -10,7 +10,7 @@ fn test_deserialize_debugid() {
             Uuid::parse_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap(),
             0,
         ),
-        serde_json::from_str("\"dfb8e43a-f242-3d73-a453-aeb6a777ef75\"").unwrap(),
+        serde_json::from_str::<DebugId>("\"dfb8e43a-f242-3d73-a453-aeb6a777ef75\"").unwrap(),
     );
 }
 
@@ -27,14 +27,47 @@ fn test_serialize_debugid() {
     );
 }
 
+#[test]
+fn test_deserialize_non_nil_debugid() {
+    let debug_id: NonNilDebugId =
+        serde_json::from_str("\"dfb8e43a-f242-3d73-a453-aeb6a777ef75\"").unwrap();
+    assert_eq!(
+        debug_id.get(),
+        DebugId::from_uuid(Uuid::parse_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap())
+    );
+
+    assert!(
+        serde_json::from_str::<NonNilDebugId>("\"00000000-0000-0000-0000-000000000000\"").is_err()
+    );
+}
+
 #[test]
 fn test_deserialize_codeid() {
     assert_eq!(
         CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into()),
-        serde_json::from_str("\"dfb8e43af2423d73a453aeb6a777ef75\"").unwrap(),
+        serde_json::from_str::<CodeId>("\"dfb8e43af2423d73a453aeb6a777ef75\"").unwrap(),
     );
 }
 
+#[test]
+fn test_deserialize_codeid_normalized() {
+    assert_eq!(
+        serde_json::from_str::<CodeId>("\"DFB8E43A-F242-3D73-A453-AEB6A777EF75\"").unwrap(),
+        CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into()),
+    );
+}
+
+#[test]
+fn test_codeid_text_json() {
+    let id = CodeId::from_text("base.apk:Xk3_Lm-9").unwrap();
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, "\"base.apk:Xk3_Lm-9\"");
+
+    let deserialized = serde_json::from_str::<CodeId>(&json).unwrap();
+    assert_eq!(deserialized, id);
+    assert_eq!(deserialized.kind(), CodeIdKind::Text);
+}
+
 #[test]
 fn test_serialize_codeid() {
     assert_eq!(
@@ -42,3 +75,75 @@ fn test_serialize_codeid() {
         serde_json::to_string(&CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into())).unwrap(),
     );
 }
+
+#[test]
+fn test_codeid_binary() {
+    let id = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    let encoded = bincode::serialize(&id).unwrap();
+    // Length prefix, tag and 16 bytes instead of 32 hex digits.
+    assert_eq!(encoded.len(), 8 + 1 + 16);
+    assert_eq!(bincode::deserialize::<CodeId>(&encoded).unwrap(), id);
+
+    for hex in &["", "5ccc38584b08000", "5CCC38584B08000", "go:abc/def"] {
+        let id = CodeId::new_preserving(hex.to_string());
+        let encoded = bincode::serialize(&id).unwrap();
+        assert_eq!(bincode::deserialize::<CodeId>(&encoded).unwrap(), id);
+    }
+
+    assert!(bincode::deserialize::<CodeId>(&[1, 0, 0, 0, 0, 0, 0, 0, 7]).is_err());
+}
+
+#[test]
+fn test_codeid_binary_kind() {
+    let id = CodeId::from_go_build_id("Xk3_Lm/9bQ-z").unwrap();
+    let decoded = bincode::deserialize::<CodeId>(&bincode::serialize(&id).unwrap()).unwrap();
+    assert_eq!(decoded, id);
+    assert_eq!(decoded.kind(), CodeIdKind::GoBuildId);
+    assert_eq!(decoded.go_build_id().as_deref(), Some("Xk3_Lm/9bQ-z"));
+
+    let id = CodeId::from_debuglink_crc(0x0a1b_2c3d);
+    let decoded = bincode::deserialize::<CodeId>(&bincode::serialize(&id).unwrap()).unwrap();
+    assert_eq!(decoded.debuglink_crc(), Some(0x0a1b_2c3d));
+
+    let id = CodeId::from_text("base.apk:Xk3_Lm-9").unwrap();
+    let decoded = bincode::deserialize::<CodeId>(&bincode::serialize(&id).unwrap()).unwrap();
+    assert_eq!(decoded.as_str(), "base.apk:Xk3_Lm-9");
+    assert_eq!(decoded.kind(), CodeIdKind::Text);
+
+    let id = CodeIdArc::from(CodeId::from_pe(0x5ccc_3858, 0x04b0_8000));
+    let decoded = bincode::deserialize::<CodeIdArc>(&bincode::serialize(&id).unwrap()).unwrap();
+    assert_eq!(decoded.kind(), CodeIdKind::PeTimestampSize);
+
+    // Kinds unknown to this version are read as opaque identifiers.
+    let decoded =
+        bincode::deserialize::<CodeId>(&[4, 0, 0, 0, 0, 0, 0, 0, 3, 99, 0, 0xab]).unwrap();
+    assert_eq!(decoded.as_str(), "ab");
+    assert_eq!(decoded.kind(), CodeIdKind::Opaque);
+}
+
+#[test]
+fn test_codeid_arc() {
+    let id = CodeIdArc::from(CodeId::new("dfb8e43af2423d73".into()));
+    assert_eq!(serde_json::to_string(&id).unwrap(), "\"dfb8e43af2423d73\"");
+    assert_eq!(
+        serde_json::from_str::<CodeIdArc>("\"dfb8e43af2423d73\"").unwrap(),
+        id
+    );
+
+    let encoded = bincode::serialize(&id).unwrap();
+    assert_eq!(encoded, bincode::serialize(&id.to_code_id()).unwrap());
+}
+
+#[test]
+fn test_fat_codeid() {
+    let mut fat = FatCodeId::new();
+    fat.insert("x86_64", CodeId::new("dfb8e43af2423d73".into()));
+    fat.insert("arm64", CodeId::new("2b36e3fc0b5a3e8c".into()));
+
+    let json = serde_json::to_string(&fat).unwrap();
+    assert_eq!(
+        json,
+        r#"{"arm64":"2b36e3fc0b5a3e8c","x86_64":"dfb8e43af2423d73"}"#
+    );
+    assert_eq!(serde_json::from_str::<FatCodeId>(&json).unwrap(), fat);
+}
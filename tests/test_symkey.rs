@@ -0,0 +1,604 @@
+use std::str::FromStr;
+
+use debugid::symkey::{
+    breakpad_sym_path, check_file_name, compressed_file_name, parse_key, tecken_sym_path,
+    CasePolicy, DebuginfodKind, KeyId, KeyOptions, SsqpKind, SymbolKey, UnifiedFileType,
+};
+use debugid::{CodeId, CodeIdKind, DebugId, ParseErrorKind};
+use uuid::Uuid;
+
+#[test]
+fn test_symstore_key() {
+    let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    assert_eq!(
+        id.symstore_key("wkernel32.pdb"),
+        "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pdb"
+    );
+
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-1a").unwrap();
+    assert_eq!(
+        id.symstore_key("C:\\projects\\Xul.pdb"),
+        "Xul.pdb/DFB8E43AF2423D73A453AEB6A777EF751A/Xul.pdb"
+    );
+    assert_eq!(
+        id.symstore_key("/tmp/build/xul.pdb"),
+        "xul.pdb/DFB8E43AF2423D73A453AEB6A777EF751A/xul.pdb"
+    );
+}
+
+#[test]
+fn test_symstore_key_pdb20() {
+    let id = DebugId::from_str("418e89c3-1").unwrap();
+    assert_eq!(id.symstore_key("old.pdb"), "old.pdb/418E89C31/old.pdb");
+}
+
+#[test]
+fn test_compressed_file_name() {
+    assert_eq!(compressed_file_name("xul.pdb"), "xul.pd_");
+    assert_eq!(compressed_file_name("ntdll.dll"), "ntdll.dl_");
+    assert_eq!(compressed_file_name("foo.exe"), "foo.ex_");
+}
+
+#[test]
+fn test_symstore_keys() {
+    let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    assert_eq!(
+        id.symstore_compressed_key("C:\\build\\wkernel32.pdb"),
+        "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pd_"
+    );
+    assert_eq!(
+        id.symstore_keys("wkernel32.pdb"),
+        [
+            "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pdb".to_string(),
+            "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pd_".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_ssqp_debug_key() {
+    let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1a").unwrap();
+    assert_eq!(
+        id.ssqp_key("C:\\build\\WKernel32.pdb"),
+        "wkernel32.pdb/ff9f9f7841db88f0cdeda9e1e9bff3b51a/wkernel32.pdb"
+    );
+}
+
+#[test]
+fn test_ssqp_code_keys() {
+    let build_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    assert_eq!(
+        build_id.ssqp_key(SsqpKind::ElfBinary, "/usr/lib/libFoo.so"),
+        "libfoo.so/elf-buildid-3ae4b8df42f2733da453aeb6a777ef7593a66d4f/libfoo.so"
+    );
+    assert_eq!(
+        build_id.ssqp_key(SsqpKind::ElfSymbols, "libfoo.so"),
+        "_.debug/elf-buildid-sym-3ae4b8df42f2733da453aeb6a777ef7593a66d4f/_.debug"
+    );
+
+    let uuid = CodeId::new("dfb8e43af2423d73a453aeb6a777ef75".into());
+    assert_eq!(
+        uuid.ssqp_key(SsqpKind::MachBinary, "libfoo.dylib"),
+        "libfoo.dylib/mach-uuid-dfb8e43af2423d73a453aeb6a777ef75/libfoo.dylib"
+    );
+    assert_eq!(
+        uuid.ssqp_key(SsqpKind::MachSymbols, "libfoo.dylib"),
+        "_.dwarf/mach-uuid-sym-dfb8e43af2423d73a453aeb6a777ef75/_.dwarf"
+    );
+
+    let pe = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert_eq!(
+        pe.ssqp_key(SsqpKind::PeBinary, "NTDLL.DLL"),
+        "ntdll.dll/5ccc38584b08000/ntdll.dll"
+    );
+}
+
+#[test]
+fn test_debuginfod_path() {
+    let code_id = CodeId::new("3AE4B8DF42F2733DA453AEB6A777EF7593A66D4F".into());
+    assert_eq!(
+        code_id.debuginfod_path(DebuginfodKind::Debuginfo),
+        "buildid/3ae4b8df42f2733da453aeb6a777ef7593a66d4f/debuginfo"
+    );
+    assert_eq!(
+        code_id.debuginfod_path(DebuginfodKind::Executable),
+        "buildid/3ae4b8df42f2733da453aeb6a777ef7593a66d4f/executable"
+    );
+}
+
+#[test]
+fn test_breakpad_sym_path() {
+    let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    assert_eq!(
+        breakpad_sym_path("C:\\build\\wkernel32.pdb", &id),
+        "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.sym"
+    );
+    assert_eq!(
+        breakpad_sym_path("XUL.PDB", &id),
+        "XUL.PDB/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/XUL.sym"
+    );
+    assert_eq!(
+        breakpad_sym_path("ntdll.dll", &id),
+        "ntdll.dll/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/ntdll.dll.sym"
+    );
+
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+    assert_eq!(
+        breakpad_sym_path("libxul.so", &id),
+        "libxul.so/DFB8E43AF2423D73A453AEB6A777EF750/libxul.so.sym"
+    );
+}
+
+#[test]
+fn test_unified_path_debug_id() {
+    let id = DebugId::from_str("DFB8E43A-F242-3D73-A453-AEB6A777EF75-A").unwrap();
+    assert_eq!(
+        id.unified_path(UnifiedFileType::Debuginfo),
+        "df/b8e43af2423d73a453aeb6a777ef75a/debuginfo"
+    );
+    assert_eq!(
+        id.unified_path(UnifiedFileType::SourceBundle),
+        "df/b8e43af2423d73a453aeb6a777ef75a/sourcebundle"
+    );
+}
+
+#[test]
+fn test_unified_path_code_id() {
+    let code_id = CodeId::new("3AE4B8DF42F2733DA453AEB6A777EF7593A66D4F".into());
+    assert_eq!(
+        code_id.unified_path(UnifiedFileType::Executable),
+        "3a/e4b8df42f2733da453aeb6a777ef7593a66d4f/executable"
+    );
+    assert_eq!(
+        code_id.unified_path(UnifiedFileType::Debuginfo),
+        "3a/e4b8df42f2733da453aeb6a777ef7593a66d4f/debuginfo"
+    );
+
+    let code_id = CodeId::new_preserving("x\u{e9}\u{2026}".into());
+    assert_eq!(
+        code_id.unified_path(UnifiedFileType::Debuginfo),
+        "x\u{e9}/\u{2026}/debuginfo"
+    );
+}
+
+#[test]
+fn test_parse_key_debug_id() {
+    let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+
+    for key in &id.symstore_keys("wkernel32.pdb") {
+        assert_eq!(
+            parse_key(key),
+            Ok(("wkernel32.pdb", KeyId::Debug(id))),
+            "{}",
+            key
+        );
+    }
+
+    let key = id.ssqp_key("wkernel32.pdb");
+    assert_eq!(parse_key(&key), Ok(("wkernel32.pdb", KeyId::Debug(id))));
+
+    let key = breakpad_sym_path("libxul.so", &id);
+    assert_eq!(parse_key(&key), Ok(("libxul.so", KeyId::Debug(id))));
+}
+
+#[test]
+fn test_parse_key_code_id() {
+    let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+
+    let key = code_id.ssqp_key(SsqpKind::ElfBinary, "libfoo.so");
+    let (name, id) = parse_key(&key).unwrap();
+    assert_eq!(name, "libfoo.so");
+    match id {
+        KeyId::Code(parsed) => {
+            assert_eq!(parsed, code_id);
+            assert_eq!(parsed.kind(), CodeIdKind::GnuBuildId);
+        }
+        KeyId::Debug(_) => panic!("expected a code id"),
+    }
+
+    let key = code_id.ssqp_key(SsqpKind::MachSymbols, "libfoo.dylib");
+    let (name, id) = parse_key(&key).unwrap();
+    assert_eq!(name, "_.dwarf");
+    match id {
+        KeyId::Code(parsed) => assert_eq!(parsed.kind(), CodeIdKind::MachUuid),
+        KeyId::Debug(_) => panic!("expected a code id"),
+    }
+
+    let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    let key = code_id.ssqp_key(SsqpKind::PeBinary, "ntdll.dll");
+    assert_eq!(
+        parse_key(&key),
+        Ok(("ntdll.dll", KeyId::Code(code_id.clone())))
+    );
+    assert_eq!(
+        parse_key("ntdll.dll/5CCC38584B08000/ntdll.dl_"),
+        Ok(("ntdll.dll", KeyId::Code(code_id)))
+    );
+}
+
+#[test]
+fn test_parse_key_invalid() {
+    let error = parse_key("").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::Empty);
+
+    let error = parse_key("/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/a.pdb").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 0)
+    );
+
+    let error = parse_key("a.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 39)
+    );
+
+    let error = parse_key("a.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/a.pdb/").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::TrailingGarbage, 45)
+    );
+
+    let error = parse_key("a.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3BX1/a.pdb").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 37)
+    );
+    assert_eq!(
+        error.input(),
+        Some("a.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3BX1/a.pdb")
+    );
+
+    let error = parse_key("a.so/elf-buildid-3ae4x/a.so").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 21)
+    );
+
+    let error = parse_key("a.dll/5ccc3858/a.dll").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::InvalidLength);
+}
+
+#[test]
+fn test_symbol_key_roundtrip() {
+    let debug_id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    let build_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    let pe_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+
+    let keys = vec![
+        SymbolKey::symstore("wkernel32.pdb", debug_id),
+        SymbolKey::ssqp_debug("wkernel32.pdb", debug_id),
+        SymbolKey::ssqp_code(SsqpKind::ElfBinary, "libfoo.so", build_id.clone()),
+        SymbolKey::ssqp_code(SsqpKind::ElfSymbols, "_.debug", build_id.clone()),
+        SymbolKey::ssqp_code(SsqpKind::MachBinary, "libfoo.dylib", build_id.clone()),
+        SymbolKey::ssqp_code(SsqpKind::MachSymbols, "_.dwarf", build_id.clone()),
+        SymbolKey::ssqp_code(SsqpKind::PeBinary, "ntdll.dll", pe_id),
+        SymbolKey::breakpad("wkernel32.pdb", debug_id),
+        SymbolKey::breakpad("libfoo.so", debug_id),
+        SymbolKey::debuginfod(DebuginfodKind::Debuginfo, build_id.clone()),
+        SymbolKey::debuginfod(DebuginfodKind::Executable, build_id.clone()),
+        SymbolKey::unified(UnifiedFileType::Executable, KeyId::Code(build_id)),
+    ];
+
+    for key in keys {
+        let string = key.to_string();
+        assert_eq!(SymbolKey::from_str(&string), Ok(key), "{}", string);
+    }
+}
+
+#[test]
+fn test_symbol_key_display() {
+    let debug_id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    let key = SymbolKey::symstore("C:\\build\\wkernel32.pdb", debug_id);
+    assert_eq!(key.to_string(), debug_id.symstore_key("wkernel32.pdb"));
+
+    let key = SymbolKey::unified(UnifiedFileType::Debuginfo, KeyId::Debug(debug_id));
+    assert_eq!(
+        key.to_string(),
+        "ff/9f9f7841db88f0cdeda9e1e9bff3b51/debuginfo"
+    );
+}
+
+#[test]
+fn test_symbol_key_parse() {
+    let key = SymbolKey::from_str("wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.pd_")
+        .unwrap();
+    match key {
+        SymbolKey::Symstore { ref name, .. } => assert_eq!(name, "wkernel32.pdb"),
+        _ => panic!("expected a symstore key, got {:?}", key),
+    }
+
+    let key = SymbolKey::from_str("ff/9f9f7841db88f0cdeda9e1e9bff3b51/debuginfo").unwrap();
+    let code_id = CodeId::new("ff9f9f7841db88f0cdeda9e1e9bff3b51".into());
+    assert_eq!(
+        key,
+        SymbolKey::unified(UnifiedFileType::Debuginfo, KeyId::Code(code_id))
+    );
+
+    let error = SymbolKey::from_str("buildid/3ae4b8df42f2733d/source").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 25)
+    );
+
+    let error = SymbolKey::from_str("buildid/3ae4x8df42f2733d/debuginfo").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 12)
+    );
+
+    let error = SymbolKey::from_str("3a/e4bx/executable").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidHex, 6)
+    );
+
+    let error = SymbolKey::from_str("ab//debuginfo").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 3)
+    );
+}
+
+#[test]
+fn test_symbol_key_case_policy() {
+    let debug_id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    let key = SymbolKey::symstore("WKernel32.pdb", debug_id);
+    assert_eq!(key.format(KeyOptions::new()), key.to_string());
+    assert_eq!(
+        key.format(KeyOptions::new().case(CasePolicy::Lowercase)),
+        "wkernel32.pdb/ff9f9f7841db88f0cdeda9e1e9bff3b51/wkernel32.pdb"
+    );
+    assert_eq!(
+        key.format(KeyOptions::new().case(CasePolicy::Uppercase)),
+        "WKERNEL32.PDB/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/WKERNEL32.PDB"
+    );
+
+    let key = SymbolKey::breakpad("XUL.PDB", debug_id);
+    assert_eq!(
+        key.format(KeyOptions::new().file_name_case(CasePolicy::Lowercase)),
+        "xul.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/xul.sym"
+    );
+
+    let build_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    let key = SymbolKey::ssqp_code(SsqpKind::ElfSymbols, "libfoo.so", build_id.clone());
+    assert_eq!(
+        key.format(KeyOptions::new().case(CasePolicy::Uppercase)),
+        "_.debug/elf-buildid-sym-3AE4B8DF42F2733DA453AEB6A777EF7593A66D4F/_.debug"
+    );
+
+    let key = SymbolKey::debuginfod(DebuginfodKind::Executable, build_id);
+    assert_eq!(
+        key.format(KeyOptions::new().id_case(CasePolicy::Uppercase)),
+        "buildid/3AE4B8DF42F2733DA453AEB6A777EF7593A66D4F/executable"
+    );
+}
+
+#[test]
+fn test_pe_file_key() {
+    let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    assert_eq!(
+        code_id.pe_file_key("ntdll.dll").as_deref(),
+        Some("ntdll.dll/5CCC38584b08000/ntdll.dll")
+    );
+
+    let code_id = CodeId::from_pe(0x0000_3858, 0x0000_8000);
+    assert_eq!(
+        code_id.pe_file_key("/mnt/c/foo.exe").as_deref(),
+        Some("foo.exe/000038588000/foo.exe")
+    );
+
+    let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    assert_eq!(code_id.pe_file_key("libfoo.so"), None);
+}
+
+#[test]
+fn test_portable_pdb_keys() {
+    let uuid = Uuid::parse_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75").unwrap();
+    let id = DebugId::portable_pdb(uuid);
+    assert_eq!(
+        id.symstore_key("Foo.pdb"),
+        "Foo.pdb/DFB8E43AF2423D73A453AEB6A777EF75FFFFFFFF/Foo.pdb"
+    );
+    assert_eq!(
+        id.ssqp_key("Foo.pdb"),
+        "foo.pdb/dfb8e43af2423d73a453aeb6a777ef75ffffffff/foo.pdb"
+    );
+
+    let key = id.symstore_key("Foo.pdb");
+    assert_eq!(parse_key(&key), Ok(("Foo.pdb", KeyId::Debug(id))));
+}
+
+#[test]
+fn test_symbol_key_percent_encode() {
+    let debug_id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    let options = KeyOptions::new().percent_encode(true);
+
+    let key = SymbolKey::Symstore {
+        name: "a/b\\c d%.pdb".into(),
+        debug_id,
+    };
+    assert_eq!(
+        key.format(options),
+        "a%2Fb%5Cc%20d%25.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/a%2Fb%5Cc%20d%25.pdb"
+    );
+
+    let key = SymbolKey::breakpad("üb.pdb", debug_id);
+    assert_eq!(
+        key.format(options),
+        "%C3%BCb.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/%C3%BCb.sym"
+    );
+    assert_eq!(
+        key.format(options.file_name_case(CasePolicy::Uppercase)),
+        "%C3%9CB.PDB/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/%C3%9CB.sym"
+    );
+
+    let key = SymbolKey::ssqp_debug("libstdc++.so.6", debug_id);
+    assert_eq!(key.format(options), key.to_string());
+
+    let key = SymbolKey::symstore("..", debug_id);
+    assert_eq!(
+        key.format(options),
+        "%2E%2E/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/%2E%2E"
+    );
+    let key = SymbolKey::symstore(".", debug_id);
+    assert_eq!(
+        key.format(options),
+        "%2E/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/%2E"
+    );
+    let key = SymbolKey::symstore("..pdb", debug_id);
+    assert_eq!(
+        key.format(options),
+        "..pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/..pdb"
+    );
+}
+
+#[test]
+fn test_check_file_name() {
+    assert!(check_file_name("wkernel32.pdb").is_ok());
+    assert!(check_file_name("libstdc++.so.6").is_ok());
+    assert!(check_file_name("_.debug").is_ok());
+
+    let error = check_file_name("").unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::Empty);
+
+    for name in &[".", ".."] {
+        let error = check_file_name(name).unwrap_err();
+        assert_eq!(
+            (error.kind(), error.offset()),
+            (ParseErrorKind::InvalidFormat, 0)
+        );
+    }
+
+    let error = check_file_name("my app.pdb").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 2)
+    );
+    assert_eq!(error.input(), Some("my app.pdb"));
+
+    let error = check_file_name("C:\\xul.pdb").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 1)
+    );
+
+    let error = check_file_name("xül.pdb").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::NonAscii, 1)
+    );
+}
+
+#[test]
+fn test_tecken_sym_path() {
+    let id = DebugId::from_str("ff9f9f78-41db-88f0-cded-a9e1e9bff3b5-1").unwrap();
+    assert_eq!(
+        tecken_sym_path("C:\\build\\WKernel32.pdb", &id),
+        "wkernel32.pdb/FF9F9F7841DB88F0CDEDA9E1E9BFF3B51/wkernel32.sym"
+    );
+
+    let id = DebugId::from_str("dfb8e43a-f242-3d73-a453-aeb6a777ef75-1f").unwrap();
+    assert_eq!(
+        breakpad_sym_path("libxul.so", &id),
+        "libxul.so/DFB8E43AF2423D73A453AEB6A777EF751f/libxul.so.sym"
+    );
+    assert_eq!(
+        tecken_sym_path("libxul.so", &id),
+        "libxul.so/DFB8E43AF2423D73A453AEB6A777EF751F/libxul.so.sym"
+    );
+
+    let key = SymbolKey::breakpad("XUL", id);
+    assert_eq!(
+        key.format(KeyOptions::tecken()),
+        "xul/DFB8E43AF2423D73A453AEB6A777EF751F/xul.sym"
+    );
+    assert_eq!(
+        SymbolKey::from_str(&key.format(KeyOptions::tecken())),
+        Ok(SymbolKey::breakpad("xul", id))
+    );
+}
+
+#[test]
+fn test_debuginfod_section_path() {
+    let code_id = CodeId::new("3AE4B8DF42F2733DA453AEB6A777EF7593A66D4F".into());
+    assert_eq!(
+        code_id.debuginfod_section_path(".debug_line"),
+        "buildid/3ae4b8df42f2733da453aeb6a777ef7593a66d4f/section/.debug_line"
+    );
+    assert_eq!(
+        code_id.debuginfod_section_path(".note.gnu+build id/ü"),
+        "buildid/3ae4b8df42f2733da453aeb6a777ef7593a66d4f/section/.note.gnu%2Bbuild%20id%2F%C3%BC"
+    );
+    assert_eq!(
+        code_id.debuginfod_section_path(".."),
+        "buildid/3ae4b8df42f2733da453aeb6a777ef7593a66d4f/section/%2E%2E"
+    );
+}
+
+#[test]
+fn test_symbol_key_debuginfod_section() {
+    let code_id = CodeId::new("3ae4b8df42f2733da453aeb6a777ef7593a66d4f".into());
+    let key = SymbolKey::debuginfod_section(code_id.clone(), ".gdb_index");
+    assert_eq!(
+        key.to_string(),
+        code_id.debuginfod_section_path(".gdb_index")
+    );
+    assert_eq!(SymbolKey::from_str(&key.to_string()), Ok(key));
+
+    let key = SymbolKey::debuginfod_section(code_id, "a b/ü");
+    assert_eq!(SymbolKey::from_str(&key.to_string()), Ok(key));
+
+    let error = SymbolKey::from_str("buildid/3ae4b8df42f2733d/section/").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 25)
+    );
+
+    let error = SymbolKey::from_str("buildid/3ae4b8df42f2733d/section/.a%2").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 35)
+    );
+
+    let error = SymbolKey::from_str("buildid/3ae4b8df42f2733d/section/%FF").unwrap_err();
+    assert_eq!(
+        (error.kind(), error.offset()),
+        (ParseErrorKind::InvalidFormat, 33)
+    );
+}
+
+#[test]
+fn test_symbol_key_symstore_code() {
+    let code_id = CodeId::from_pe(0x5ccc_3858, 0x04b0_8000);
+    let key = SymbolKey::symstore_code("C:\\Windows\\NTDLL.dll", code_id.clone());
+    let string = code_id.pe_file_key("NTDLL.dll").unwrap();
+    assert_eq!(key.to_string(), string);
+    assert_eq!(string, "NTDLL.dll/5CCC38584b08000/NTDLL.dll");
+    assert_eq!(SymbolKey::from_str(&string), Ok(key));
+
+    let key = SymbolKey::ssqp_code(SsqpKind::PeBinary, "ntdll.dll", code_id.clone());
+    assert_eq!(SymbolKey::from_str(&key.to_string()), Ok(key));
+
+    let key = SymbolKey::symstore_code("foo.exe", CodeId::from_pe(0x1234_5678, 0x8000));
+    assert_eq!(key.to_string(), "foo.exe/123456788000/foo.exe");
+    assert_eq!(SymbolKey::from_str(&key.to_string()), Ok(key));
+}
+
+#[test]
+fn test_symbol_key_digit_only_id() {
+    let key = SymbolKey::from_str("Foo.pdb/123456781/Foo.pdb").unwrap();
+    assert_eq!(
+        key,
+        SymbolKey::symstore("Foo.pdb", DebugId::from_pdb20(0x1234_5678, 1))
+    );
+    assert_eq!(key.to_string(), "Foo.pdb/123456781/Foo.pdb");
+
+    let id = DebugId::from_str("12345678-1234-1234-1234-123456789012-1").unwrap();
+    let key = SymbolKey::from_str("foo.pdb/123456781234123412341234567890121/foo.pdb").unwrap();
+    assert_eq!(key, SymbolKey::symstore("foo.pdb", id));
+    assert_eq!(
+        key.to_string(),
+        "foo.pdb/123456781234123412341234567890121/foo.pdb"
+    );
+}